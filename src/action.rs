@@ -2,6 +2,7 @@ use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, Display, EnumString};
 
 use crate::card::{Card, Suit, Rank};
+use crate::error::EuchreError;
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Copy, Clone, EnumIter, Display, EnumString)]
 pub enum Action {
@@ -113,11 +114,21 @@ pub enum Action {
     CTDiscard = 52,
     #[strum(ascii_case_insensitive)]
     C9Discard = 53,
+    #[strum(ascii_case_insensitive)]
+    PickAlone = 54,
+    #[strum(ascii_case_insensitive)]
+    CallHAlone = 55,
+    #[strum(ascii_case_insensitive)]
+    CallDAlone = 56,
+    #[strum(ascii_case_insensitive)]
+    CallSAlone = 57,
+    #[strum(ascii_case_insensitive)]
+    CallCAlone = 58,
 }
 
 impl Action {
     /// Map all \<Card\>Play and \<Card\>Discard actions to the \<Card\> they refer to.
-    pub fn action_to_card(action: Action) -> Result<Card, String> {
+    pub fn action_to_card(action: Action) -> Result<Card, EuchreError> {
         // the number % 6 returns the rank
         // 0 1 2 3 4 5
         // A K Q J T 9
@@ -125,11 +136,11 @@ impl Action {
         // 1 2 3 4
         // H D S C
         let num: u8 = action as u8;
-        if num < 6 {
-            return Err(format!("The action {:?} has no mapping to a Card", action))
+        if !(6..=53).contains(&num) {
+            return Err(EuchreError::NoCardForAction(action))
         }
         let num_rank: u8 = num % 6;
-        let num_suit: u8 = (((if num > 29 {num + 6} else {num}) % 30)) / 6;
+        let num_suit: u8 = ((if num > 29 {num + 6} else {num}) % 30) / 6;
         
         let rank: Rank = match num_rank {
             0 => Rank::Ace,
@@ -152,14 +163,15 @@ impl Action {
         Ok(Card::new(suit, rank))
     }
 
-    /// Map a Card to either a play or discard action.
-    pub fn card_to_action(card: &Card, is_play: bool) -> Action {
+    /// Map a Card to either a play or discard action.  
+    /// Errors if the card has an unset suit or rank.
+    pub fn card_to_action(card: &Card, is_play: bool) -> Result<Action, EuchreError> {
         let suit_num: u8 = match card.suit() {
             Suit::Hearts => 1,
             Suit::Diamonds => 2,
             Suit::Spades => 3,
             Suit::Clubs => 4,
-            Suit::Unset => panic!("Unset Suit should be impossible here."),
+            Suit::Unset => return Err(EuchreError::UnsetCard(*card)),
         };
         let rank_num: u8 = match card.rank() {
             Rank::Ace => 0,
@@ -168,25 +180,65 @@ impl Action {
             Rank::Jack => 3,
             Rank::Ten => 4,
             Rank::Nine => 5,
-            Rank::Unset => panic!("Unset Rank should be impossible here."),
+            Rank::Unset => return Err(EuchreError::UnsetCard(*card)),
         };
         let discard_offset: u8 = if is_play {0} else {24};
         let num: u8 = rank_num + (suit_num * 6) + discard_offset;
-        Action::from_integer(num).unwrap()
+        Action::from_integer(num)
+    }
+
+    /// Returns whether this action orders up or calls trump alone.
+    pub fn is_alone(&self) -> bool {
+        (*self as u8) > 53
+    }
+
+    /// Returns whether this action plays a card to the center.
+    pub fn is_play(&self) -> bool {
+        (6..=29).contains(&(*self as u8))
+    }
+
+    /// Returns whether this action is the dealer discarding a card.
+    pub fn is_discard(&self) -> bool {
+        (30..=53).contains(&(*self as u8))
+    }
+
+    /// Returns the suit named by a second round call, alone or not.
+    pub fn called_suit(&self) -> Option<Suit> {
+        match self {
+            Action::CallH | Action::CallHAlone => Some(Suit::Hearts),
+            Action::CallD | Action::CallDAlone => Some(Suit::Diamonds),
+            Action::CallS | Action::CallSAlone => Some(Suit::Spades),
+            Action::CallC | Action::CallCAlone => Some(Suit::Clubs),
+            _ => None,
+        }
+    }
+
+    /// Returns the second round call naming `suit`, alone or not.
+    /// Returns `None` if `suit` is unset.
+    pub fn call(suit: Suit, alone: bool) -> Option<Action> {
+        let call: Action = match suit {
+            Suit::Hearts => Action::CallH,
+            Suit::Diamonds => Action::CallD,
+            Suit::Spades => Action::CallS,
+            Suit::Clubs => Action::CallC,
+            Suit::Unset => return None,
+        };
+        if alone {Self::from_integer(call as u8 + 53).ok()} else {Some(call)}
     }
 
-    pub fn from_integer(val: u8) -> Result<Action, String> {
+    pub fn from_integer(val: u8) -> Result<Action, EuchreError> {
         for act in Action::iter() {
             if act as u8 == val {
                 return Ok(act)
             }
         }
-        Err(format!("Integer {:?} has no corresponding Action.", val))
+        Err(EuchreError::NoActionForInteger(val))
     }
 }
 
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FlippedChoice {
     PickedUp,
     TurnedDown,
@@ -208,7 +260,30 @@ mod tests {
 
     #[test]
     fn action_from_invalid_int() {
-        assert_eq!(Action::from_integer(54).unwrap_err(), "Integer 54 has no corresponding Action.");
+        assert_eq!(Action::from_integer(59).unwrap_err().to_string(), "Integer 59 has no corresponding Action.");
+    }
+
+    #[test]
+    fn action_kinds() {
+        assert!(Action::HAPlay.is_play() && Action::C9Play.is_play());
+        assert!(!Action::HADiscard.is_play() && !Action::Pick.is_play());
+        assert!(Action::HADiscard.is_discard() && Action::C9Discard.is_discard());
+        assert!(!Action::C9Play.is_discard() && !Action::PickAlone.is_discard());
+        assert_eq!(Action::CallD.called_suit(), Some(Suit::Diamonds));
+        assert_eq!(Action::CallCAlone.called_suit(), Some(Suit::Clubs));
+        assert_eq!(Action::Pick.called_suit(), None);
+        assert_eq!(Action::call(Suit::Spades, true), Some(Action::CallSAlone));
+        assert_eq!(Action::call(Suit::Hearts, false), Some(Action::CallH));
+        assert_eq!(Action::call(Suit::Unset, false), None);
+    }
+
+    #[test]
+    fn alone_actions() {
+        assert_eq!(Action::from_integer(54).unwrap(), Action::PickAlone);
+        assert!(Action::CallSAlone.is_alone());
+        assert!(!Action::CallS.is_alone());
+        assert!(Action::action_to_card(Action::CallCAlone).is_err());
+        assert_eq!(Action::CallHAlone, Action::from_str("callhalone").unwrap());
     }
 
     #[test]
@@ -220,6 +295,15 @@ mod tests {
         Action::action_to_card(Action::HAPlay).unwrap());
     }
 
+    #[test]
+    fn card_to_action() {
+        assert_eq!(Action::card_to_action(&Card::new(Suit::Clubs, Rank::Nine), true).unwrap(), Action::C9Play);
+        assert_eq!(Action::card_to_action(&Card::new(Suit::Hearts, Rank::Ace), false).unwrap(), Action::HADiscard);
+        assert_eq!(Action::card_to_action(&Card::new(Suit::Unset, Rank::Ace), true).unwrap_err(),
+                   EuchreError::UnsetCard(Card::new(Suit::Unset, Rank::Ace)));
+        assert!(Action::card_to_action(&Card::new(Suit::Clubs, Rank::Unset), false).is_err());
+    }
+
     #[test]
     fn string_to_action() {
         assert_eq!(Action::C9Play, Action::from_str("c9PlAy").unwrap());
@@ -0,0 +1,368 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+use strum::IntoEnumIterator;
+
+use crate::action::{Action, FlippedChoice};
+use crate::card::{Card, Rank, Suit};
+use crate::dealer::DealSpec;
+use crate::game::EuchreGame;
+use crate::game::scoped_state::ScopedGameState;
+use crate::judger::Judger;
+
+/// Times to try dealing the unseen cards while honoring every known void before giving up on voids.
+const VOID_ATTEMPTS: usize = 100;
+
+/// Everything the current player of a `ScopedGameState` can infer about the cards they can't see,
+/// used to deal the unseen cards into hands that could really be out there ("determinizing").
+///
+/// Known facts honored by every sample:
+/// 1. Cards in the player's hand, played so far, thrown away by them, or turned face up
+///    are never dealt to anyone else.
+/// 2. Every other player holds one card for each trick they have left to play,
+///    and a player sitting out holds none.
+/// 3. A player who didn't follow suit holds no more cards of the suit that was led.
+/// 4. A dealer who picked up the flipped card still holds it,
+///    unless they have played it or shown out of its suit.
+#[derive(Debug, Clone)]
+pub struct Determinizer {
+    me: u8,
+    hand: Vec<Card>,
+    trump: Option<Suit>,
+    /// Cards nobody is known to hold
+    unseen: Vec<Card>,
+    /// How many unseen cards each player holds
+    capacity: [usize; 4],
+    /// Suits each player is known to be out of
+    voids: [Vec<Suit>; 4],
+    /// A flipped card picked up by another dealer who hasn't played it yet
+    dealer_card: Option<(u8, Card)>,
+    tricks_won: [u8; 2],
+}
+
+impl Determinizer {
+    /// Gather what the current player of `state` knows.
+    pub fn new(state: &ScopedGameState) -> Determinizer {
+        let me: u8 = state.current_actor;
+        let sitting_out: Option<u8> = state.alone_actor.map(|x| (x + 2) % 4);
+        let center: Vec<Card> = state.center.clone().unwrap_or_default();
+        let mut voids: [Vec<Suit>; 4] = Default::default();
+        let mut tricks_won: [u8; 2] = [0, 0];
+
+        // Replay every finished trick to learn who won it and who showed out
+        if let Some(trump) = state.trump {
+            let active: Vec<u8> = (0..4).filter(|x| Some(*x) != sitting_out).collect();
+            let played: usize = state.previous_played.iter().map(|x| x.len()).sum();
+            let finished: usize = (played - center.len()) / active.len();
+            let mut leader: u8 = (1..=4).map(|x| (state.dealer_actor + x) % 4).find(|x| Some(*x) != sitting_out).unwrap();
+
+            for trick in 0..finished {
+                let order: Vec<u8> = (0..4).map(|x| (leader + x) % 4).filter(|x| Some(*x) != sitting_out).collect();
+                let cards: Vec<Card> = order.iter().map(|x| state.previous_played[usize::from(*x)][trick]).collect();
+                Self::note_voids(&mut voids, trump, &order, &cards);
+                leader = Judger::new().judge_trick(trump, &cards, &order);
+                tricks_won[usize::from(leader % 2)] += 1;
+            }
+            Self::note_voids(&mut voids, trump, &state.order, &center);
+        }
+
+        let picked_up: bool = state.flipped_choice == Some(FlippedChoice::PickedUp) && sitting_out != Some(state.dealer_actor);
+        let seen: Vec<Card> = state.previous_played.concat();
+        let dealer_card: Option<(u8, Card)> = Some((state.dealer_actor, state.flipped_card))
+            .filter(|(dealer, card)| picked_up && *dealer != me && !seen.contains(card))
+            .filter(|(dealer, card)| {
+                state.previous_played[usize::from(*dealer)].len() < 5
+                    && state.trump.is_none_or(|x| !voids[usize::from(*dealer)].contains(&card.effective_suit(x)))
+            });
+        let unseen: Vec<Card> = Suit::iter().filter(|x| *x != Suit::Unset)
+            .flat_map(|suit| Rank::iter().filter(|x| *x != Rank::Unset).map(move |rank| Card::new(suit, rank)))
+            .filter(|x| !state.hand.contains(x) && !seen.contains(x) && *x != state.flipped_card && Some(*x) != state.discarded)
+            .collect();
+
+        let mut capacity: [usize; 4] = [0; 4];
+        for id in (0..4).filter(|x| *x != me && Some(*x) != sitting_out) {
+            capacity[usize::from(id)] = 5 - state.previous_played[usize::from(id)].len();
+            if dealer_card.is_some_and(|(dealer, _)| dealer == id) {
+                capacity[usize::from(id)] -= 1;
+            }
+        }
+
+        Determinizer {
+            me,
+            hand: state.hand.clone(),
+            trump: state.trump,
+            unseen,
+            capacity,
+            voids,
+            dealer_card,
+            tricks_won,
+        }
+    }
+
+    /// Record the suits players showed out of in a trick played by `order`.
+    fn note_voids(voids: &mut [Vec<Suit>; 4], trump: Suit, order: &[u8], cards: &[Card]) {
+        let Some(led) = cards.first().map(|x| x.effective_suit(trump)) else {
+            return
+        };
+        for (id, card) in order.iter().zip(cards).skip(1) {
+            let void: &mut Vec<Suit> = &mut voids[usize::from(*id)];
+            if card.effective_suit(trump) != led && !void.contains(&led) {
+                void.push(led);
+            }
+        }
+    }
+
+    /// Tricks each team has won so far, team 0 being players 0 & 2.
+    pub fn tricks_won(&self) -> [u8; 2] {
+        self.tricks_won
+    }
+
+    /// Suits `player_id` is known to hold no cards of.
+    pub fn voids(&self, player_id: u8) -> &[Suit] {
+        &self.voids[usize::from(player_id)]
+    }
+
+    /// Deal the unseen cards into a random set of hands consistent with everything known,
+    /// returning every player's current hand indexed by player id.
+    /// If no deal honoring every void is found after a number of tries, the dealer is assumed
+    /// to have discarded the flipped card instead of keeping it, and after that voids are ignored.
+    pub fn sample(&self, gen: &mut ChaCha8Rng) -> Vec<Vec<Card>> {
+        (0..VOID_ATTEMPTS)
+            .find_map(|_| self.try_sample(gen, true, true))
+            .or_else(|| (0..VOID_ATTEMPTS).find_map(|_| self.try_sample(gen, false, true)))
+            .or_else(|| self.try_sample(gen, false, false))
+            .unwrap()
+    }
+
+    /// Deal a random game consistent with everything known, see `sample`,
+    /// and replay it up to `state`, which must be the state this was built from.
+    /// Cards the current player never saw, like the dealer's discard, are dealt randomly.
+    /// The game is played by the rules of `state`.
+    pub fn sample_game(&self, state: &ScopedGameState, gen: &mut ChaCha8Rng) -> EuchreGame {
+        let dealer: u8 = state.dealer_actor;
+        let flipped: Card = state.flipped_card;
+        let sitting_out: Option<u8> = state.alone_actor.map(|x| (x + 2) % 4);
+        let picked_up: bool = state.flipped_choice == Some(FlippedChoice::PickedUp) && sitting_out != Some(dealer);
+
+        // Everyone was dealt the cards they hold and have played, except the flipped card the dealer picked up
+        let mut spec: DealSpec = DealSpec::new().flipped(flipped);
+        let mut dealer_cards: Vec<Card> = vec![];
+        let mut discard: Option<Card> = None;
+        for (id, mut cards) in self.sample(gen).into_iter().enumerate() {
+            cards.extend(&state.previous_played[id]);
+            if picked_up && id == usize::from(dealer) {
+                cards.extend(state.discarded);
+                if cards.contains(&flipped) {
+                    cards.retain(|x| *x != flipped);
+                } else {
+                    discard = Some(flipped);
+                }
+                dealer_cards = cards.clone();
+            }
+            spec = spec.hand(id as u8, &cards);
+        }
+
+        let mut game: EuchreGame = EuchreGame::with_deal_spec(Some(dealer), Some(gen.gen()), state.rules, &spec)
+            .expect("A sampled deal should always be valid");
+        for action in Self::bids(state) {
+            game.step(action).expect("Bids rebuilt from a state should be legal");
+        }
+
+        // A dealer who hasn't discarded yet is the current player, holding six cards
+        if picked_up && !(dealer == self.me && state.hand.len() == 6) {
+            let card: Card = state.discarded.or(discard).unwrap_or_else(|| {
+                *game.get_hands()[usize::from(dealer)].iter().find(|x| **x != flipped && !dealer_cards.contains(x)).unwrap()
+            });
+            game.step(Action::card_to_action(&card, false).unwrap()).expect("The sampled discard should be legal");
+        }
+
+        let mut played: [usize; 4] = [0; 4];
+        loop {
+            let id: usize = usize::from(game.get_curr_player_id());
+            let Some(card) = state.previous_played[id].get(played[id]) else {
+                break
+            };
+            game.step(Action::card_to_action(card, true).unwrap()).expect("Plays rebuilt from a state should be legal");
+            played[id] += 1;
+        }
+        game
+    }
+
+    /// The bidding that led to `state`: passes starting left of the dealer,
+    /// then the pick up or call that decided trump, if any.
+    fn bids(state: &ScopedGameState) -> Vec<Action> {
+        let bidder: u8 = state.calling_actor.unwrap_or(state.current_actor);
+        let mut passes: u8 = (bidder + 3 - state.dealer_actor) % 4;
+        if state.flipped_choice == Some(FlippedChoice::TurnedDown) {
+            passes += 4;
+        }
+
+        let mut bids: Vec<Action> = vec![Action::Pass; usize::from(passes)];
+        let alone: bool = state.alone_actor.is_some();
+        match (state.trump, state.flipped_choice) {
+            (Some(_), Some(FlippedChoice::PickedUp)) => bids.push(if alone {Action::PickAlone} else {Action::Pick}),
+            (Some(trump), _) => bids.push(Action::call(trump, alone).unwrap()),
+            _ => (),
+        }
+        bids
+    }
+
+    /// Deal the unseen cards one at a time to a random player with room for them,
+    /// the rest going to the kitty. Returns `None` on reaching a card nobody can hold.
+    fn try_sample(&self, gen: &mut ChaCha8Rng, keep_dealer_card: bool, honor_voids: bool) -> Option<Vec<Vec<Card>>> {
+        let mut hands: Vec<Vec<Card>> = vec![vec![]; 4];
+        hands[usize::from(self.me)] = self.hand.clone();
+        let mut room: [usize; 4] = self.capacity;
+        if let Some((dealer, card)) = self.dealer_card {
+            if keep_dealer_card {
+                hands[usize::from(dealer)].push(card);
+            } else {
+                room[usize::from(dealer)] += 1;
+            }
+        }
+        let mut kitty: usize = self.unseen.len() - room.iter().sum::<usize>();
+
+        let mut cards: Vec<Card> = self.unseen.clone();
+        cards.shuffle(gen);
+        for card in cards {
+            let suit: Option<Suit> = self.trump.map(|x| card.effective_suit(x));
+            let takers: Vec<usize> = (0..4)
+                .filter(|x| room[*x] > 0)
+                .filter(|x| !honor_voids || suit.is_none_or(|s| !self.voids[*x].contains(&s)))
+                .collect();
+
+            // Pick a player, or the kitty, with odds matching how much room each has left
+            let open: usize = takers.iter().map(|x| room[*x]).sum::<usize>() + kitty;
+            if open == 0 {
+                return None
+            }
+            let mut pick: usize = gen.gen_range(0..open);
+            match takers.iter().find(|x| {
+                    let found: bool = pick < room[**x];
+                    pick = pick.saturating_sub(room[**x]);
+                    found
+                }) {
+                Some(id) => {
+                    hands[*id].push(card);
+                    room[*id] -= 1;
+                },
+                None => kitty -= 1,
+            }
+        }
+        if room.iter().any(|x| *x > 0) {
+            return None
+        }
+        Some(hands)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+    use crate::rules::RuleSet;
+
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(text_card).collect()
+    }
+
+    fn text_card(text: &str) -> Card {
+        std::str::FromStr::from_str(text).unwrap()
+    }
+
+    /// Dealer 0 picks up the heart nine; player 1 leads the spade ace, which player 2 can't follow.
+    fn game_after_first_lead() -> EuchreGame {
+        let spec: DealSpec = DealSpec::new()
+            .hand(0, &cards("HJ HA CA CK CQ"))
+            .hand(1, &cards("SA SK DA DK DQ"))
+            .hand(2, &cards("H9 DJ CJ C9 CT"))
+            .hand(3, &cards("S9 ST HK D9 DT"))
+            .flipped(text_card("HT"));
+        let moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pass), (3, Action::Pass), (0, Action::Pick),
+                                            (0, Action::CQDiscard), (1, Action::SAPlay), (2, Action::C9Play)];
+        EuchreGame::from_moves(Some(0), Some(1), RuleSet::default(), &spec, &moves).unwrap()
+    }
+
+    #[test]
+    fn samples_are_consistent() {
+        let game: EuchreGame = game_after_first_lead();
+        let state: ScopedGameState = game.get_state();
+        assert_eq!(state.current_actor, 3);
+        let determinizer: Determinizer = Determinizer::new(&state);
+        assert_eq!(determinizer.voids(2), &[Suit::Spades]);
+
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..50 {
+            let hands: Vec<Vec<Card>> = determinizer.sample(&mut gen);
+            assert_eq!(hands[3], state.hand);
+            assert_eq!(hands.iter().map(|x| x.len()).collect::<Vec<usize>>(), vec![5, 4, 4, 5]);
+            // The dealer kept the heart ten they picked up
+            assert!(hands[0].contains(&text_card("HT")));
+            assert!(!hands[2].iter().any(|x| x.effective_suit(Suit::Hearts) == Suit::Spades));
+
+            let mut all: Vec<Card> = hands.concat();
+            all.extend(cards("SA C9"));
+            all.sort_by_key(|x| (x.suit() as u8, x.rank() as u8));
+            all.dedup();
+            assert_eq!(all.len(), 20);
+        }
+    }
+
+    #[test]
+    fn dealer_remembers_their_discard() {
+        let mut game: EuchreGame = game_after_first_lead();
+        game.step(Action::S9Play).unwrap();
+        let state: ScopedGameState = game.get_state();
+        assert_eq!((state.current_actor, state.discarded), (0, Some(text_card("CQ"))));
+
+        let determinizer: Determinizer = Determinizer::new(&state);
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..50 {
+            let hands: Vec<Vec<Card>> = determinizer.sample(&mut gen);
+            assert!(!hands.concat().contains(&text_card("CQ")));
+            let sampled: EuchreGame = determinizer.sample_game(&state, &mut gen);
+            assert_eq!(sampled.get_state().discarded, state.discarded);
+        }
+    }
+
+    #[test]
+    fn counts_tricks_won() {
+        let mut game: EuchreGame = game_after_first_lead();
+        game.step(Action::S9Play).unwrap();
+        game.step(Action::HTPlay).unwrap();
+        game.step(Action::HAPlay).unwrap();
+
+        // The dealer trumped the first trick, and the heart ten is no longer assumed to be in their hand
+        let determinizer: Determinizer = Determinizer::new(&game.get_state());
+        assert_eq!(determinizer.tricks_won(), [1, 0]);
+        assert_eq!(determinizer.voids(0), &[Suit::Spades]);
+        let hands: Vec<Vec<Card>> = determinizer.sample(&mut ChaCha8Rng::seed_from_u64(0));
+        assert_eq!(hands.iter().map(|x| x.len()).collect::<Vec<usize>>(), vec![3, 4, 4, 4]);
+    }
+
+    /// The parts of a state that don't depend on the order cards sit in a hand.
+    fn unordered(mut state: ScopedGameState) -> ScopedGameState {
+        state.hand.sort_by_key(|x| (x.suit() as u8, x.rank() as u8));
+        state.legal_actions.sort_by_key(|x| *x as u8);
+        state
+    }
+
+    #[test]
+    fn sampled_games_match_the_state() {
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(3);
+        for seed in 0..40 {
+            let rules: RuleSet = RuleSet { stick_the_dealer: seed % 3 != 0, ..RuleSet::default() };
+            let mut game: EuchreGame = EuchreGame::new(None, Some(seed), rules);
+            while !game.is_over() {
+                let state: ScopedGameState = game.get_state();
+                let sampled: EuchreGame = Determinizer::new(&state).sample_game(&state, &mut gen);
+                assert_eq!(unordered(sampled.get_state()), unordered(state.clone()), "seed {}", seed);
+
+                let action: Action = *state.legal_actions.choose(&mut gen).unwrap();
+                game.step(action).unwrap();
+            }
+        }
+    }
+}
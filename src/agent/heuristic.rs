@@ -0,0 +1,410 @@
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::card::{Card, Rank, Suit};
+use crate::game::scoped_state::ScopedGameState;
+use crate::judger::Judger;
+
+/// The numbers a `HeuristicAgent` bids with.
+///
+/// A hand is scored for a possible trump suit by adding up the value of its cards:
+/// the bowers, every other trump, and aces of other suits.
+/// The agent orders up, calls or goes alone when that score reaches the matching threshold.
+/// Change the defaults with struct update syntax:
+///
+/// ```
+/// use euchre_rs::agent::heuristic::{HeuristicAgent, HeuristicThresholds};
+///
+/// let agent = HeuristicAgent::with_thresholds(HeuristicThresholds { order_up: 9, ..HeuristicThresholds::default() });
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeuristicThresholds {
+    /// Value of holding the jack of trump
+    pub right_bower: u8,
+    /// Value of holding the jack of the same color as trump
+    pub left_bower: u8,
+    /// Value of every other trump card
+    pub trump: u8,
+    /// Value of an ace that isn't trump
+    pub off_ace: u8,
+    /// Score needed to order up the flipped card in the first round.
+    /// The flipped card counts for the dealer's team and against the other team.
+    pub order_up: u8,
+    /// Score needed to call a suit in the second round
+    pub call: u8,
+    /// Score needed to go alone instead, when the rules allow it
+    pub go_alone: u8,
+}
+
+impl Default for HeuristicThresholds {
+    fn default() -> Self {
+        HeuristicThresholds {
+            right_bower: 4,
+            left_bower: 3,
+            trump: 2,
+            off_ace: 1,
+            order_up: 8,
+            call: 8,
+            go_alone: 13,
+        }
+    }
+}
+
+/// An agent that bids and plays by common euchre rules of thumb.
+///
+/// Bidding: order up or call when the hand scores well enough, see `HeuristicThresholds`.
+/// Discarding: keep trump and aces, and throw away a lone card to create a void if possible.
+/// Leading: lead trump when partner called, otherwise an off-suit ace, otherwise a low card.
+/// Following: second hand plays low, third hand plays high, the last player wins as cheaply as possible,
+/// nobody overtakes their partner, and a player who can't follow suit trumps in.
+pub struct HeuristicAgent {
+    thresholds: HeuristicThresholds,
+}
+
+impl Agent for HeuristicAgent {
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        let legal: &Vec<Action> = &state.legal_actions;
+        let choice: Option<Action> = if legal.iter().any(|x| x.is_play()) {
+            self.play(state)
+        } else if legal.iter().any(|x| x.is_discard()) {
+            self.discard(state)
+        } else if legal.contains(&Action::Pick) || legal.contains(&Action::PickAlone) {
+            self.order_up(state)
+        } else {
+            self.call(state)
+        };
+
+        choice.filter(|x| legal.contains(x)).unwrap_or(legal[0])
+    }
+}
+
+impl HeuristicAgent {
+    /// Create an agent with the default thresholds.
+    pub fn new() -> Self {
+        Self::with_thresholds(HeuristicThresholds::default())
+    }
+
+    pub fn with_thresholds(thresholds: HeuristicThresholds) -> Self {
+        HeuristicAgent { thresholds }
+    }
+
+    pub fn thresholds(&self) -> HeuristicThresholds {
+        self.thresholds
+    }
+
+    /// The bidding value of a single card if `trump` were trump.
+    fn card_value(&self, card: Card, trump: Suit) -> i32 {
+        let value: u8 = if card.is_right(trump) {
+            self.thresholds.right_bower
+        } else if card.is_left(trump) {
+            self.thresholds.left_bower
+        } else if card.suit() == trump {
+            self.thresholds.trump
+        } else if card.rank() == Rank::Ace {
+            self.thresholds.off_ace
+        } else {
+            0
+        };
+        i32::from(value)
+    }
+
+    /// The bidding score of `hand` if `trump` were trump.
+    fn hand_score(&self, hand: &[Card], trump: Suit) -> i32 {
+        hand.iter().map(|x| self.card_value(*x, trump)).sum()
+    }
+
+    /// Decide whether to order up the flipped card in the first round.
+    fn order_up(&self, state: &ScopedGameState) -> Option<Action> {
+        let trump: Suit = state.flipped_card.suit();
+        let flipped: i32 = self.card_value(state.flipped_card, trump);
+        let score: i32 = if state.dealer_actor == state.current_actor {
+            // The dealer picks up the flipped card and discards their weakest card
+            let weakest: i32 = state.hand.iter().map(|x| self.card_value(*x, trump)).min().unwrap_or(0);
+            self.hand_score(&state.hand, trump) + flipped - weakest
+        } else if state.dealer_actor % 2 == state.current_actor % 2 {
+            self.hand_score(&state.hand, trump) + flipped
+        } else {
+            self.hand_score(&state.hand, trump) - flipped
+        };
+
+        self.bid(score, i32::from(self.thresholds.order_up), Action::Pick, Action::PickAlone, state)
+    }
+
+    /// Decide whether to call a suit in the second round, and which.
+    /// When the dealer is stuck, the best suit is called whatever its score.
+    fn call(&self, state: &ScopedGameState) -> Option<Action> {
+        let (action, score) = state.legal_actions.iter()
+            .filter(|x| !x.is_alone())
+            .filter_map(|x| x.called_suit().map(|suit| (*x, self.hand_score(&state.hand, suit))))
+            .max_by_key(|(_, score)| *score)?;
+        let alone: Action = Action::call(action.called_suit()?, true)?;
+
+        let threshold: i32 = if state.legal_actions.contains(&Action::Pass) {i32::from(self.thresholds.call)} else {i32::MIN};
+        self.bid(score, threshold, action, alone, state)
+    }
+
+    /// Choose between passing, `action` and `alone` given the hand's `score`.
+    fn bid(&self, score: i32, threshold: i32, action: Action, alone: Action, state: &ScopedGameState) -> Option<Action> {
+        if score >= i32::from(self.thresholds.go_alone) && state.legal_actions.contains(&alone) {
+            Some(alone)
+        } else if score >= threshold {
+            Some(action)
+        } else {
+            Some(Action::Pass)
+        }
+    }
+
+    /// Discard the weakest card, preferring one that leaves the dealer void in its suit.
+    fn discard(&self, state: &ScopedGameState) -> Option<Action> {
+        let trump: Suit = state.trump?;
+        let hand: &Vec<Card> = &state.hand;
+        let off_suit: Vec<Card> = hand.iter().copied().filter(|x| x.effective_suit(trump) != trump).collect();
+        let count = |suit: Suit| off_suit.iter().filter(|x| x.suit() == suit).count();
+
+        let card: Card = off_suit.iter().copied()
+            .filter(|x| x.rank() != Rank::Ace && count(x.suit()) == 1)
+            .min_by_key(|x| x.rank() as u8)
+            .or_else(|| off_suit.iter().copied().filter(|x| x.rank() != Rank::Ace).min_by_key(|x| x.rank() as u8))
+            .or_else(|| off_suit.iter().copied().min_by_key(|x| x.rank() as u8))
+            .or_else(|| lowest(hand, trump, None))?;
+        Action::card_to_action(&card, false).ok()
+    }
+
+    /// Choose a card to play, see the rules of thumb on `HeuristicAgent`.
+    fn play(&self, state: &ScopedGameState) -> Option<Action> {
+        let trump: Suit = state.trump?;
+        let playable: Vec<Card> = state.legal_actions.iter().filter_map(|x| Action::action_to_card(*x).ok()).collect();
+        let center: &[Card] = state.center.as_deref().unwrap_or(&[]);
+
+        let card: Card = if center.is_empty() {
+            self.lead(state, &playable, trump)?
+        } else {
+            let led: Suit = center[0].effective_suit(trump);
+            let winner: u8 = Judger::new().judge_trick(trump, center, &state.order[..center.len()]);
+            let best: Card = center[state.order.iter().position(|x| *x == winner)?];
+            let partner_winning: bool = winner % 2 == state.current_actor % 2;
+            let last_to_play: bool = center.len() + 1 == state.order.len();
+            let can_follow: bool = playable.iter().any(|x| x.effective_suit(trump) == led);
+            let winners: Vec<Card> = playable.iter().copied().filter(|x| best.is_lower(trump, *x)).collect();
+
+            if partner_winning || winners.is_empty() {
+                lowest(&playable, trump, Some(led))?
+            } else if !can_follow || last_to_play {
+                // Trump in, or take the trick as cheaply as possible
+                lowest(&winners, trump, Some(led))?
+            } else if center.len() == 1 {
+                // Second hand low
+                lowest(&playable, trump, Some(led))?
+            } else {
+                // Third hand high
+                highest(&playable, trump, Some(led))?
+            }
+        };
+        Action::card_to_action(&card, true).ok()
+    }
+
+    /// Choose a card to lead a trick.
+    fn lead(&self, state: &ScopedGameState, playable: &[Card], trump: Suit) -> Option<Card> {
+        let partner_called: bool = state.calling_actor == Some((state.current_actor + 2) % 4);
+        let trumps: Vec<Card> = playable.iter().copied().filter(|x| x.effective_suit(trump) == trump).collect();
+        let off_suit: Vec<Card> = playable.iter().copied().filter(|x| x.effective_suit(trump) != trump).collect();
+
+        if partner_called && !trumps.is_empty() {
+            return highest(&trumps, trump, None)
+        }
+        off_suit.iter().copied().find(|x| x.rank() == Rank::Ace)
+            .or_else(|| lowest(&off_suit, trump, None))
+            .or_else(|| highest(&trumps, trump, None))
+    }
+}
+
+impl Default for HeuristicAgent {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How strong `card` is in a trick where `led` was led, for ordering cards.
+fn strength(card: Card, trump: Suit, led: Option<Suit>) -> u8 {
+    let rank: u8 = card.rank() as u8;
+    if card.is_right(trump) {
+        50
+    } else if card.is_left(trump) {
+        49
+    } else if card.suit() == trump {
+        30 + rank
+    } else if Some(card.suit()) == led {
+        15 + rank
+    } else {
+        rank
+    }
+}
+
+fn lowest(cards: &[Card], trump: Suit, led: Option<Suit>) -> Option<Card> {
+    cards.iter().copied().min_by_key(|x| strength(*x, trump, led))
+}
+
+fn highest(cards: &[Card], trump: Suit, led: Option<Suit>) -> Option<Card> {
+    cards.iter().copied().max_by_key(|x| strength(*x, trump, led))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::action::FlippedChoice;
+    use crate::agent::random::RandomAgent;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+    use crate::rules::RuleSet;
+
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(|x| Card::from_str(x).unwrap()).collect()
+    }
+
+    fn card(text: &str) -> Card {
+        Card::from_str(text).unwrap()
+    }
+
+    /// A bidding state for `current_actor` with `dealer_actor` dealing.
+    fn bid_state(hand: &str, flipped: &str, current_actor: u8, dealer_actor: u8, legal_actions: Vec<Action>) -> ScopedGameState {
+        ScopedGameState {
+            dealer_actor,
+            current_actor,
+            hand: cards(hand),
+            calling_actor: None,
+            alone_actor: None,
+            flipped_choice: if legal_actions.contains(&Action::Pick) {None} else {Some(FlippedChoice::TurnedDown)},
+            flipped_card: card(flipped),
+            trump: None,
+            led_suit: None,
+            order: vec![],
+            center: None,
+            previous_played: vec![vec![]; 4],
+            discarded: None,
+            legal_actions,
+            rules: RuleSet::default(),
+        }
+    }
+
+    /// A state where player 0 plays after `center` was played by the players before them,
+    /// with clubs trump called by `calling_actor`.
+    fn play_state(hand: &str, center: &str, calling_actor: u8) -> ScopedGameState {
+        let trump: Suit = Suit::Clubs;
+        let hand: Vec<Card> = cards(hand);
+        let center: Vec<Card> = cards(center);
+        let led: Option<Suit> = center.first().map(|x| x.effective_suit(trump));
+        let following: Vec<Card> = hand.iter().copied().filter(|x| Some(x.effective_suit(trump)) == led).collect();
+        let playable: &Vec<Card> = if following.is_empty() {&hand} else {&following};
+        let order: Vec<u8> = (0..4).map(|x| (4 - center.len() as u8 + x) % 4).collect();
+
+        ScopedGameState {
+            dealer_actor: 3,
+            current_actor: 0,
+            legal_actions: playable.iter().map(|x| Action::card_to_action(x, true).unwrap()).collect(),
+            hand,
+            calling_actor: Some(calling_actor),
+            alone_actor: None,
+            flipped_choice: Some(FlippedChoice::PickedUp),
+            flipped_card: card("C9"),
+            trump: Some(trump),
+            led_suit: led,
+            order,
+            center: if center.is_empty() {None} else {Some(center)},
+            previous_played: vec![vec![]; 4],
+            discarded: None,
+            rules: RuleSet::default(),
+        }
+    }
+
+    #[test]
+    fn orders_up_strong_hands() {
+        let first_round: Vec<Action> = vec![Action::Pass, Action::Pick, Action::PickAlone];
+        let mut agent: HeuristicAgent = HeuristicAgent::new();
+
+        // right, ace of trump and an off ace, with partner dealing: 4 + 2 + 1 + 2 for the flipped card
+        assert_eq!(agent.decide_action(&bid_state("HJ HA SA D9 DT", "H9", 0, 2, first_round.clone())), Action::Pick);
+        // the same hand against an opposing dealer isn't enough
+        assert_eq!(agent.decide_action(&bid_state("HJ HA SA D9 DT", "H9", 0, 1, first_round.clone())), Action::Pass);
+        // both bowers, ace and king of trump plus an off ace
+        assert_eq!(agent.decide_action(&bid_state("HJ DJ HA HK SA", "H9", 0, 2, first_round.clone())), Action::PickAlone);
+
+        let cautious: HeuristicThresholds = HeuristicThresholds { order_up: 12, ..HeuristicThresholds::default() };
+        let mut agent: HeuristicAgent = HeuristicAgent::with_thresholds(cautious);
+        assert_eq!(agent.decide_action(&bid_state("HJ HA SA D9 DT", "H9", 0, 2, first_round)), Action::Pass);
+    }
+
+    #[test]
+    fn calls_best_suit() {
+        let second_round: Vec<Action> = vec![Action::Pass, Action::CallD, Action::CallS, Action::CallC];
+        let mut agent: HeuristicAgent = HeuristicAgent::new();
+
+        assert_eq!(agent.decide_action(&bid_state("SJ CJ SA SK D9", "H9", 1, 0, second_round.clone())), Action::CallS);
+        assert_eq!(agent.decide_action(&bid_state("S9 CT DA HK D9", "H9", 1, 0, second_round)), Action::Pass);
+
+        // stuck dealer calls the best suit left
+        let stuck: Vec<Action> = vec![Action::CallD, Action::CallS, Action::CallC];
+        assert_eq!(agent.decide_action(&bid_state("S9 CT DA DK D9", "H9", 0, 0, stuck)), Action::CallD);
+    }
+
+    #[test]
+    fn discard_creates_void() {
+        let mut state: ScopedGameState = bid_state("CJ CA C9 DK DQ SQ", "C9", 0, 0, vec![]);
+        state.trump = Some(Suit::Clubs);
+        state.legal_actions = state.hand.iter().map(|x| Action::card_to_action(x, false).unwrap()).collect();
+        assert_eq!(HeuristicAgent::new().decide_action(&state), Action::SQDiscard);
+
+        // no lone card to throw away, so the lowest off-suit card that isn't an ace goes
+        state.hand = cards("CJ CA C9 DK DQ SA");
+        state.legal_actions = state.hand.iter().map(|x| Action::card_to_action(x, false).unwrap()).collect();
+        assert_eq!(HeuristicAgent::new().decide_action(&state), Action::DQDiscard);
+    }
+
+    #[test]
+    fn leads_trump_when_partner_called() {
+        let mut agent: HeuristicAgent = HeuristicAgent::new();
+        assert_eq!(agent.decide_action(&play_state("C9 SJ HA D9 DT", "", 2)), Action::SJPlay);
+        assert_eq!(agent.decide_action(&play_state("C9 SJ HA D9 DT", "", 1)), Action::HAPlay);
+        assert_eq!(agent.decide_action(&play_state("C9 SJ HK D9 DT", "", 1)), Action::D9Play);
+    }
+
+    #[test]
+    fn follows_by_position() {
+        let mut agent: HeuristicAgent = HeuristicAgent::new();
+        // second hand low
+        assert_eq!(agent.decide_action(&play_state("HA H9 CJ D9 DT", "HK", 0)), Action::H9Play);
+        // third hand high
+        assert_eq!(agent.decide_action(&play_state("HA H9 CJ D9 DT", "HT HK", 0)), Action::HAPlay);
+        // last hand wins cheaply, or doesn't overtake partner
+        assert_eq!(agent.decide_action(&play_state("HA HQ H9 D9 DT", "HJ HT H9", 0)), Action::HQPlay);
+        assert_eq!(agent.decide_action(&play_state("HA HQ H9 D9 DT", "HT HK H9", 0)), Action::H9Play);
+    }
+
+    #[test]
+    fn trumps_in_when_void() {
+        let mut agent: HeuristicAgent = HeuristicAgent::new();
+        assert_eq!(agent.decide_action(&play_state("CJ C9 SA D9 DT", "HA", 1)), Action::C9Play);
+        // overtrumps with the lowest trump that wins
+        assert_eq!(agent.decide_action(&play_state("CJ CQ CT D9 DT", "HA C9", 1)), Action::CTPlay);
+        // sluffs when partner is winning
+        assert_eq!(agent.decide_action(&play_state("CJ C9 SA D9 DT", "HA H9", 1)), Action::D9Play);
+    }
+
+    #[test]
+    fn beats_random_players() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(HeuristicAgent::new()),
+                                                Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(HeuristicAgent::new()),
+                                                Box::new(RandomAgent::new(Some(3)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, None, Some(0), false));
+        let mut points: [u32; 2] = [0, 0];
+        for hand in 0..400u16 {
+            let rewards: Vec<u8> = env.run_with_dealer(Some((hand % 4) as u8));
+            points[0] += u32::from(rewards[0]);
+            points[1] += u32::from(rewards[1]);
+        }
+        assert!(points[0] > 2 * points[1], "{:?}", points);
+    }
+}
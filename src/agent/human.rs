@@ -38,6 +38,7 @@ impl<R: BufRead> Agent for HumanAgent<R> {
 impl HumanAgent<StdinLock<'static>> {
 
     /// Create a `HumanAgent` object that gets input from `stdin`.
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             reader: stdin().lock()
@@ -63,7 +64,7 @@ where R: BufRead {
     pub fn new(reader: R) -> Self
     {
         Self{
-            reader: reader
+            reader
         }
     }
 
@@ -0,0 +1,292 @@
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+use rand_chacha::ChaCha8Rng;
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::agent::determinize::Determinizer;
+use crate::game::EuchreGame;
+use crate::game::scoped_state::ScopedGameState;
+use crate::rules::RuleSet;
+use crate::utils::get_rdm_gen;
+
+/// How long an `IsmctsAgent` searches, and how it balances trying new moves against repeating good ones.
+/// Change the defaults with struct update syntax:
+///
+/// ```
+/// use std::time::Duration;
+/// use euchre_rs::agent::ismcts::{IsmctsAgent, IsmctsConfig};
+///
+/// let config = IsmctsConfig { iterations: usize::MAX, time_limit: Some(Duration::from_millis(200)), ..IsmctsConfig::default() };
+/// let agent = IsmctsAgent::with_config(config, Some(7));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IsmctsConfig {
+    /// Games simulated per decision
+    pub iterations: usize,
+    /// Stop simulating once a decision has taken this long, even if iterations are left
+    pub time_limit: Option<Duration>,
+    /// Weight of the exploration term when choosing moves in the tree.
+    /// Rewards are scaled to lie between -1 and 1.
+    pub exploration: f64,
+}
+
+impl Default for IsmctsConfig {
+    fn default() -> Self {
+        IsmctsConfig {
+            iterations: 1000,
+            time_limit: None,
+            exploration: 0.7,
+        }
+    }
+}
+
+/// An edge of the search tree: an action as the searching player sees it.
+/// Discards by other players are hidden, so they all share the edge `None`.
+type Edge = Option<Action>;
+
+/// A node of the search tree, reached by taking the actions on the path to it from the root.
+struct Node {
+    /// Who took the action leading to this node
+    player: u8,
+    visits: u32,
+    /// How many times this node's action was legal when its parent was visited
+    available: u32,
+    /// Sum of the rewards `player` got in the games played through this node
+    reward: f64,
+    children: Vec<(Edge, usize)>,
+}
+
+impl Node {
+    fn new(player: u8) -> Node {
+        Node { player, visits: 0, available: 0, reward: 0.0, children: vec![] }
+    }
+
+    fn child(&self, edge: Edge) -> Option<usize> {
+        self.children.iter().find(|(x, _)| *x == edge).map(|(_, id)| *id)
+    }
+}
+
+/// An agent using Information Set Monte Carlo Tree Search (single observer ISMCTS).
+///
+/// Unlike `PimcAgent`, which solves each sampled deal on its own, every simulated game is played
+/// on the same tree of the moves this player can see, so a move is judged by how it does
+/// across all the deals still possible rather than by the best play for each deal.
+/// This avoids "strategy fusion", where a player assumes they'll know which deal they're in.
+///
+/// Every iteration:
+/// 1. Deals a random game consistent with what the player knows, see `Determinizer::sample_game`
+/// 2. Walks down the tree by UCB1 among the moves legal in that deal
+/// 3. Adds one new move to the tree, then plays randomly to the end of the hand
+/// 4. Credits every move on the path with the points its player's team won minus the other team's
+///
+/// Bidding and discarding are searched the same way as playing cards.
+/// The most visited move is taken. Games are simulated by the rules of the game being played, `state.rules`.
+pub struct IsmctsAgent {
+    config: IsmctsConfig,
+    gen: ChaCha8Rng,
+}
+
+impl Agent for IsmctsAgent {
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        let legal: &Vec<Action> = &state.legal_actions;
+        if legal.len() == 1 {
+            return legal[0]
+        }
+
+        let determinizer: Determinizer = Determinizer::new(state);
+        let mut tree: Vec<Node> = vec![Node::new(state.current_actor)];
+        let start: Instant = Instant::now();
+        for _ in 0..self.config.iterations {
+            if self.config.time_limit.is_some_and(|x| start.elapsed() >= x) {
+                break
+            }
+            self.iterate(&mut tree, state, &determinizer);
+        }
+
+        // The first of the most visited actions, so ties are broken the same way every time
+        let visits = |action: &Action| tree[0].child(Some(*action)).map_or(0, |x| tree[x].visits);
+        let most: u32 = legal.iter().map(visits).max().unwrap();
+        *legal.iter().find(|x| visits(x) == most).unwrap()
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.gen = get_rdm_gen(Some(seed));
+    }
+}
+
+impl IsmctsAgent {
+    /// Create an agent searching with the default config.
+    pub fn new(seed: Option<u64>) -> Self {
+        Self::with_config(IsmctsConfig::default(), seed)
+    }
+
+    /// Create an agent searching with `config`.
+    pub fn with_config(config: IsmctsConfig, seed: Option<u64>) -> Self {
+        IsmctsAgent {
+            config,
+            gen: get_rdm_gen(seed),
+        }
+    }
+
+    /// The config this agent searches with.
+    pub fn config(&self) -> IsmctsConfig {
+        self.config
+    }
+
+    /// Play one simulated game from `state` and record its result in `tree`.
+    fn iterate(&mut self, tree: &mut Vec<Node>, state: &ScopedGameState, determinizer: &Determinizer) {
+        let me: u8 = state.current_actor;
+        let mut game: EuchreGame = determinizer.sample_game(state, &mut self.gen);
+        let mut path: Vec<usize> = vec![0];
+        let mut node: usize = 0;
+
+        while !game.is_over() {
+            let player: u8 = game.get_curr_player_id();
+            let legal: Vec<Action> = game.get_legal_actions();
+            let mut edges: Vec<Edge> = legal.iter().map(|x| Self::edge(me, player, *x)).collect();
+            edges.dedup();
+            let children: Vec<usize> = edges.iter().filter_map(|x| tree[node].child(*x)).collect();
+            for child in children {
+                tree[child].available += 1;
+            }
+
+            let untried: Vec<Edge> = edges.iter().filter(|x| tree[node].child(**x).is_none()).copied().collect();
+            if let Some(edge) = untried.choose(&mut self.gen) {
+                let child: usize = tree.len();
+                tree.push(Node { available: 1, ..Node::new(player) });
+                tree[node].children.push((*edge, child));
+                self.step(&mut game, *edge, &legal);
+                path.push(child);
+                break
+            }
+
+            let (edge, child): (Edge, usize) = edges.iter()
+                .map(|x| (*x, tree[node].child(*x).unwrap()))
+                .max_by(|(_, a), (_, b)| self.ucb(&tree[*a]).total_cmp(&self.ucb(&tree[*b])))
+                .unwrap();
+            self.step(&mut game, edge, &legal);
+            path.push(child);
+            node = child;
+        }
+
+        while !game.is_over() {
+            let action: Action = *game.get_legal_actions().choose(&mut self.gen).unwrap();
+            game.step(action).unwrap();
+        }
+
+        let points: Vec<u8> = game.get_rewards().unwrap();
+        for id in path {
+            let node: &mut Node = &mut tree[id];
+            node.visits += 1;
+            node.reward += Self::reward(state, &points, node.player);
+        }
+    }
+
+    /// How `me` sees `player` taking `action`.
+    fn edge(me: u8, player: u8, action: Action) -> Edge {
+        if action.is_discard() && player != me {None} else {Some(action)}
+    }
+
+    /// Take the action behind `edge`, picking a random discard for a hidden one.
+    fn step(&mut self, game: &mut EuchreGame, edge: Edge, legal: &[Action]) {
+        let action: Action = edge.unwrap_or_else(|| *legal.choose(&mut self.gen).unwrap());
+        game.step(action).unwrap();
+    }
+
+    /// The UCB1 score of choosing `node`, using how often it was available instead of parent visits.
+    fn ucb(&self, node: &Node) -> f64 {
+        let visits: f64 = f64::from(node.visits);
+        node.reward / visits + self.config.exploration * (f64::from(node.available).ln() / visits).sqrt()
+    }
+
+    /// Points won by `player`'s team minus the other team's, scaled by the most points a hand can score.
+    fn reward(state: &ScopedGameState, points: &[u8], player: u8) -> f64 {
+        let rules: RuleSet = state.rules;
+        let most: u8 = [rules.make_points, rules.march_points, rules.euchre_points, rules.alone_march_points]
+            .into_iter().max().unwrap().max(1);
+        (f64::from(points[usize::from(player)]) - f64::from(points[usize::from((player + 1) % 4)])) / f64::from(most)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::card::Card;
+    use crate::dealer::DealSpec;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+
+    fn config(iterations: usize) -> IsmctsConfig {
+        IsmctsConfig { iterations, ..IsmctsConfig::default() }
+    }
+
+    #[test]
+    fn same_seed_same_choices() {
+        let mut game: EuchreGame = EuchreGame::new(Some(2), Some(8), RuleSet::default());
+        let mut first: IsmctsAgent = IsmctsAgent::with_config(config(40), Some(5));
+        let mut second: IsmctsAgent = IsmctsAgent::with_config(config(40), Some(5));
+        while !game.is_over() {
+            let state: ScopedGameState = game.get_state();
+            let action: Action = first.decide_action(&state);
+            assert_eq!(action, second.decide_action(&state));
+            assert!(state.legal_actions.contains(&action));
+            game.step(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn orders_up_a_lock() {
+        // Player 1 holds the top five hearts, so ordering up alone marches every time
+        let hand: Vec<Card> = "HJ DJ HA HK HQ".split_whitespace().map(|x| Card::from_str(x).unwrap()).collect();
+        let spec: DealSpec = DealSpec::new().hand(1, &hand).flipped(Card::from_str("H9").unwrap());
+        let game: EuchreGame = EuchreGame::with_deal_spec(Some(0), Some(5), RuleSet::default(), &spec).unwrap();
+        let mut agent: IsmctsAgent = IsmctsAgent::with_config(config(300), Some(1));
+        assert_eq!(agent.decide_action(&game.get_state()), Action::PickAlone);
+    }
+
+    #[test]
+    fn searches_by_the_games_rules() {
+        // The same lock, in a game where marching alone is worth less than marching with a partner
+        let rules: RuleSet = RuleSet { alone_march_points: 1, ..RuleSet::default() };
+        let hand: Vec<Card> = "HJ DJ HA HK HQ".split_whitespace().map(|x| Card::from_str(x).unwrap()).collect();
+        let spec: DealSpec = DealSpec::new().hand(1, &hand).flipped(Card::from_str("H9").unwrap());
+        let game: EuchreGame = EuchreGame::with_deal_spec(Some(0), Some(5), rules, &spec).unwrap();
+        let mut agent: IsmctsAgent = IsmctsAgent::with_config(config(300), Some(1));
+        assert_eq!(agent.decide_action(&game.get_state()), Action::Pick);
+    }
+
+    #[test]
+    fn stops_at_the_time_limit() {
+        let limit: Duration = Duration::from_millis(50);
+        let mut agent: IsmctsAgent = IsmctsAgent::with_config(IsmctsConfig { iterations: usize::MAX, time_limit: Some(limit), ..IsmctsConfig::default() }, Some(2));
+        let game: EuchreGame = EuchreGame::new(Some(0), Some(4), RuleSet::default());
+
+        let start: Instant = Instant::now();
+        agent.decide_action(&game.get_state());
+        assert!(start.elapsed() < limit * 20);
+    }
+
+    #[test]
+    fn beats_random_players() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(IsmctsAgent::with_config(config(100), Some(0))),
+                                                Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(IsmctsAgent::with_config(config(100), Some(2))),
+                                                Box::new(RandomAgent::new(Some(3)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, None, None, false));
+        let mut points: [u32; 2] = [0, 0];
+        for hand in 0..16u64 {
+            env.config.seed = Some(hand);
+            let rewards: Vec<u8> = env.run();
+            points[0] += u32::from(rewards[0]);
+            points[1] += u32::from(rewards[1]);
+        }
+        assert!(points[0] > 2 * points[1], "{:?}", points);
+    }
+}
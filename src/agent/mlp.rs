@@ -0,0 +1,479 @@
+//! A policy network agent, running a small multilayer perceptron trained elsewhere.
+//!
+//! # Features
+//! `encode` turns a `ScopedGameState` into `FEATURE_LEN` numbers, each 0 or 1.
+//! Seats are relative to the current player: 0 is the current player, 1 the player to their left,
+//! 2 their partner and 3 the player to their right.
+//! Cards are indexed by suit then rank, `6 * suit + rank - 9`, with suits in the order D, H, C, S
+//! and ranks from 9 to A, so `D9` is 0 and `SA` is 23.
+//!
+//! | Offset | Length | Meaning |
+//! |--------|--------|---------|
+//! | 0      | 24     | Cards in the current player's hand |
+//! | 24     | 24     | The flipped card |
+//! | 48     | 4      | Seat of the dealer |
+//! | 52     | 4      | Trump suit, all 0 while bidding |
+//! | 56     | 4      | Seat of the player who called trump |
+//! | 60     | 4      | Seat of the player going alone |
+//! | 64     | 1      | The flipped card was picked up |
+//! | 65     | 1      | The flipped card was turned down |
+//! | 66     | 4      | Suit led to the current trick |
+//! | 70     | 96     | Cards each seat has played this hand, 24 per seat, including the current trick |
+//! | 166    | 24     | Cards in the current trick |
+//! | 190    | 4      | Seat of the player who leads the current trick, all 0 while bidding |
+//!
+//! # Network
+//! The network is a stack of dense layers taking `FEATURE_LEN` inputs and giving `ACTION_COUNT` outputs,
+//! one logit per action numbered as `Action as u8`. Every layer but the last is followed by a ReLU.
+//!
+//! # Weights file
+//! A text file of whitespace separated numbers:
+//! ```text
+//! euchre-mlp 1
+//! dense <inputs> <outputs>
+//! <one line per output: its weight for every input>
+//! <one line: the bias of every output>
+//! dense <inputs> <outputs>
+//! ...
+//! ```
+//! Layers are listed from input to output. Blank lines and lines starting with `#` are ignored.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::action::{Action, FlippedChoice};
+use crate::agent::Agent;
+use crate::card::{Card, Suit};
+use crate::error::EuchreError;
+use crate::game::scoped_state::ScopedGameState;
+use crate::utils::get_rdm_gen;
+
+/// Number of features `encode` produces.
+pub const FEATURE_LEN: usize = 194;
+/// Number of actions, and of network outputs.
+pub const ACTION_COUNT: usize = 59;
+
+/// First line of a weights file.
+const FILE_HEADER: &str = "euchre-mlp 1";
+
+/// Encode `state` from the current player's point of view, see the module docs for the layout.
+pub fn encode(state: &ScopedGameState) -> Vec<f32> {
+    let me: u8 = state.current_actor;
+    let mut features: Vec<f32> = vec![0.0; FEATURE_LEN];
+    let mut set = |offset: usize, index: usize| features[offset + index] = 1.0;
+    let seat = |id: u8| usize::from((id + 4 - me) % 4);
+    let suit = |suit: Suit| suit as usize;
+
+    for card in &state.hand {
+        set(0, card_index(card));
+    }
+    set(24, card_index(&state.flipped_card));
+    set(48, seat(state.dealer_actor));
+    if let Some(trump) = state.trump {
+        set(52, suit(trump));
+        set(190, seat(state.order[0]));
+    }
+    if let Some(caller) = state.calling_actor {
+        set(56, seat(caller));
+    }
+    if let Some(alone) = state.alone_actor {
+        set(60, seat(alone));
+    }
+    match state.flipped_choice {
+        Some(FlippedChoice::PickedUp) => set(64, 0),
+        Some(FlippedChoice::TurnedDown) => set(65, 0),
+        None => (),
+    }
+    if let Some(led) = state.led_suit {
+        set(66, suit(led));
+    }
+    for (id, cards) in state.previous_played.iter().enumerate() {
+        for card in cards {
+            set(70 + 24 * seat(id as u8), card_index(card));
+        }
+    }
+    for card in state.center.iter().flatten() {
+        set(166, card_index(card));
+    }
+    features
+}
+
+/// Index of `card` in the feature vector's card blocks.
+fn card_index(card: &Card) -> usize {
+    6 * card.suit() as usize + card.rank() as usize - 9
+}
+
+/// A fully connected layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dense {
+    inputs: usize,
+    /// `weights[o * inputs + i]` is output `o`'s weight for input `i`
+    weights: Vec<f32>,
+    biases: Vec<f32>,
+}
+
+impl Dense {
+    /// Create a layer from one row of weights per output.
+    /// Errors if the rows aren't all the same length, or there isn't one bias per row.
+    pub fn new(weights: Vec<Vec<f32>>, biases: Vec<f32>) -> Result<Dense, EuchreError> {
+        let inputs: usize = weights.first().map_or(0, |x| x.len());
+        if weights.len() != biases.len() || weights.iter().any(|x| x.len() != inputs) {
+            return Err(EuchreError::InvalidNetwork(format!("A layer needs one bias and {} weights per output", inputs)))
+        }
+        Ok(Dense { inputs, weights: weights.concat(), biases })
+    }
+
+    /// Number of values the layer takes.
+    pub fn inputs(&self) -> usize {
+        self.inputs
+    }
+
+    /// Number of values the layer gives.
+    pub fn outputs(&self) -> usize {
+        self.biases.len()
+    }
+
+    fn forward(&self, input: &[f32]) -> Vec<f32> {
+        self.biases.iter().enumerate()
+            .map(|(o, bias)| bias + self.weights[o * self.inputs..(o + 1) * self.inputs].iter().zip(input).map(|(w, x)| w * x).sum::<f32>())
+            .collect()
+    }
+}
+
+/// A multilayer perceptron mapping encoded states to action logits, see the module docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mlp {
+    layers: Vec<Dense>,
+}
+
+impl Mlp {
+    /// Stack `layers` from input to output.
+    /// Errors if there are no layers, a layer's inputs don't match the previous layer's outputs,
+    /// or the network doesn't take `FEATURE_LEN` inputs and give `ACTION_COUNT` outputs.
+    pub fn new(layers: Vec<Dense>) -> Result<Mlp, EuchreError> {
+        let (Some(first), Some(last)) = (layers.first(), layers.last()) else {
+            return Err(EuchreError::InvalidNetwork(String::from("A network needs at least one layer")))
+        };
+        if first.inputs() != FEATURE_LEN || last.outputs() != ACTION_COUNT {
+            return Err(EuchreError::InvalidNetwork(format!("A network must take {} inputs and give {} outputs, got {} and {}",
+                                                           FEATURE_LEN, ACTION_COUNT, first.inputs(), last.outputs())))
+        }
+        if let Some(i) = (1..layers.len()).find(|i| layers[*i].inputs() != layers[i - 1].outputs()) {
+            return Err(EuchreError::InvalidNetwork(format!("Layer {} takes {} inputs but the layer before gives {} outputs",
+                                                           i, layers[i].inputs(), layers[i - 1].outputs())))
+        }
+        Ok(Mlp { layers })
+    }
+
+    /// The layers, from input to output.
+    pub fn layers(&self) -> &[Dense] {
+        &self.layers
+    }
+
+    /// Run the network on an encoded state, returning one logit per action.
+    pub fn forward(&self, input: &[f32]) -> Vec<f32> {
+        let mut values: Vec<f32> = input.to_vec();
+        for (i, layer) in self.layers.iter().enumerate() {
+            values = layer.forward(&values);
+            if i + 1 < self.layers.len() {
+                values.iter_mut().for_each(|x| *x = x.max(0.0));
+            }
+        }
+        values
+    }
+
+    /// Load a network from a weights file.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Mlp> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Save the network as a weights file, replacing the file at `path` if it exists.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer: BufWriter<File> = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Read a network in the weights file format.
+    /// Errors with `InvalidData` if the file is malformed or the network doesn't fit the game.
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Mlp> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines: Vec<String> = vec![];
+        for line in reader.lines() {
+            let line: String = line?;
+            if !line.trim().is_empty() && !line.trim_start().starts_with('#') {
+                lines.push(line);
+            }
+        }
+        let mut lines = lines.iter().map(|x| x.trim());
+        if lines.next() != Some(FILE_HEADER) {
+            return Err(invalid(format!("Weights file must start with {:?}", FILE_HEADER)))
+        }
+
+        let numbers = |line: Option<&str>, count: usize| -> io::Result<Vec<f32>> {
+            let line: &str = line.ok_or_else(|| invalid(String::from("Weights file ends in the middle of a layer")))?;
+            let values: Vec<f32> = line.split_whitespace()
+                .map(|x| x.parse::<f32>().map_err(|_| invalid(format!("{:?} is not a number", x))))
+                .collect::<io::Result<Vec<f32>>>()?;
+            if values.len() != count {
+                return Err(invalid(format!("Expected {} numbers, got {} in {:?}", count, values.len(), line)))
+            }
+            Ok(values)
+        };
+
+        let mut layers: Vec<Dense> = vec![];
+        while let Some(line) = lines.next() {
+            let sizes: Vec<usize> = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["dense", inputs, outputs] => [inputs, outputs].iter().map(|x| x.parse::<usize>()).collect::<Result<Vec<usize>, _>>()
+                    .map_err(|_| invalid(format!("{:?} has invalid layer sizes", line)))?,
+                _ => return Err(invalid(format!("Expected a layer header, got {:?}", line))),
+            };
+            let weights: Vec<Vec<f32>> = (0..sizes[1]).map(|_| numbers(lines.next(), sizes[0])).collect::<io::Result<Vec<Vec<f32>>>>()?;
+            let biases: Vec<f32> = numbers(lines.next(), sizes[1])?;
+            layers.push(Dense::new(weights, biases).map_err(|e| invalid(e.to_string()))?);
+        }
+        Mlp::new(layers).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Write the network in the weights file format.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{}", FILE_HEADER)?;
+        for layer in &self.layers {
+            writeln!(writer, "dense {} {}", layer.inputs(), layer.outputs())?;
+            for row in layer.weights.chunks(layer.inputs.max(1)) {
+                writeln!(writer, "{}", Self::join(row))?;
+            }
+            writeln!(writer, "{}", Self::join(&layer.biases))?;
+        }
+        Ok(())
+    }
+
+    fn join(values: &[f32]) -> String {
+        let mut text: String = String::new();
+        for (i, value) in values.iter().enumerate() {
+            let _ = write!(text, "{}{}", if i == 0 {""} else {" "}, value);
+        }
+        text
+    }
+}
+
+/// How an `MlpAgent` picks among the legal actions once their logits are known.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyChoice {
+    /// Take the legal action with the highest logit, the lowest numbered one if several tie
+    Greedy,
+    /// Sample a legal action from the softmax of the legal logits divided by `temperature`,
+    /// which must be positive and finite.
+    /// Falls back to the greedy choice if the logits can't be turned into weights, e.g. when they're infinite
+    Sampled { temperature: f32 },
+}
+
+/// An agent choosing actions with an `Mlp` policy network.
+///
+/// Every decision encodes the state with `encode`, runs the network, throws away the logits
+/// of illegal actions, and chooses among the rest according to its `PolicyChoice`.
+pub struct MlpAgent {
+    network: Mlp,
+    choice: PolicyChoice,
+    gen: ChaCha8Rng,
+}
+
+impl Agent for MlpAgent {
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        let logits: Vec<f32> = self.network.forward(&encode(state));
+        let mut legal: Vec<(Action, f32)> = state.legal_actions.iter().map(|x| (*x, logits[*x as usize])).collect();
+        legal.sort_by_key(|(x, _)| *x as u8);
+
+        let most: f32 = legal.iter().map(|(_, x)| *x).fold(f32::NEG_INFINITY, f32::max);
+        let greedy: Action = legal.iter().find(|(_, x)| *x == most).unwrap_or(&legal[0]).0;
+        match self.choice {
+            PolicyChoice::Greedy => greedy,
+            PolicyChoice::Sampled { temperature } => {
+                let weights: Vec<f32> = legal.iter().map(|(_, x)| ((x - most) / temperature).exp()).collect();
+                let sum: f32 = weights.iter().sum();
+                if !(sum.is_finite() && sum > 0.0) {
+                    return greedy
+                }
+                let mut pick: f32 = self.gen.gen_range(0.0..sum);
+                for ((action, _), weight) in legal.iter().zip(&weights) {
+                    if pick < *weight {
+                        return *action
+                    }
+                    pick -= weight;
+                }
+                legal.last().unwrap().0
+            },
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.gen = get_rdm_gen(Some(seed));
+    }
+}
+
+impl MlpAgent {
+    /// Create an agent choosing with `choice`; `seed` is only used when sampling.  
+    /// Returns an error if a sampling temperature isn't positive and finite.
+    pub fn new(network: Mlp, choice: PolicyChoice, seed: Option<u64>) -> Result<Self, EuchreError> {
+        if let PolicyChoice::Sampled { temperature } = choice {
+            if !(temperature > 0.0 && temperature.is_finite()) {
+                return Err(EuchreError::InvalidAgentConfig(format!("Sampling temperature must be positive and finite, got {}", temperature)))
+            }
+        }
+        Ok(MlpAgent {
+            network,
+            choice,
+            gen: get_rdm_gen(seed),
+        })
+    }
+
+    pub fn network(&self) -> &Mlp {
+        &self.network
+    }
+
+    pub fn choice(&self) -> PolicyChoice {
+        self.choice
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::dealer::DealSpec;
+    use crate::game::EuchreGame;
+    use crate::rules::RuleSet;
+
+    /// A network that ignores its input, scoring each action by its bias in `biases`, or 0.
+    fn constant_network(biases: &[(Action, f32)]) -> Mlp {
+        let mut bias: Vec<f32> = vec![0.0; ACTION_COUNT];
+        for (action, value) in biases {
+            bias[*action as usize] = *value;
+        }
+        Mlp::new(vec![Dense::new(vec![vec![0.0; FEATURE_LEN]; ACTION_COUNT], bias).unwrap()]).unwrap()
+    }
+
+    /// Dealer 3 picks up the heart nine, and player 0 leads the spade ace.
+    fn pinned_state() -> ScopedGameState {
+        let cards = |text: &str| text.split_whitespace().map(|x| Card::from_str(x).unwrap()).collect::<Vec<Card>>();
+        let spec: DealSpec = DealSpec::new()
+            .hand(0, &cards("SA S9 D9 DT DQ"))
+            .hand(1, &cards("HJ DJ HT CA C9"))
+            .flipped(Card::from_str("H9").unwrap());
+        let moves: Vec<(u8, Action)> = vec![(0, Action::Pass), (1, Action::Pass), (2, Action::Pass), (3, Action::Pick),
+                                            (3, Action::H9Discard), (0, Action::SAPlay)];
+        EuchreGame::from_moves(Some(3), Some(1), RuleSet::default(), &spec, &moves).unwrap().get_state()
+    }
+
+    #[test]
+    fn encoding_layout() {
+        let state: ScopedGameState = pinned_state();
+        assert_eq!(state.current_actor, 1);
+        let features: Vec<f32> = encode(&state);
+        assert_eq!(features.len(), FEATURE_LEN);
+
+        let ones: Vec<usize> = (0..FEATURE_LEN).filter(|x| features[*x] == 1.0).collect();
+        let mut expected: Vec<usize> = vec![
+            6 + 2, 2, 6 + 1, 12 + 5, 12, // HJ DJ HT CA C9 in hand
+            24 + 6, // flipped H9
+            48 + 2, // dealer is partner
+            52 + 1, // hearts are trump
+            56 + 2, // partner called
+            64, // picked up
+            66 + 3, // spades led
+            70 + 3 * 24 + 18 + 5, // SA played by the player to the right
+            166 + 18 + 5, // SA in the center
+            190 + 3, // the player to the right led
+        ];
+        expected.sort();
+        assert_eq!(ones, expected);
+    }
+
+    #[test]
+    fn forward_pass() {
+        // Hidden unit 0 counts the cards in hand minus 3, unit 1 is its negation and is cut off by the ReLU
+        let mut hidden: Vec<Vec<f32>> = vec![vec![0.0; FEATURE_LEN]; 2];
+        hidden[0][..24].fill(1.0);
+        hidden[1][..24].fill(-1.0);
+        let mut output: Vec<Vec<f32>> = vec![vec![1.0, 1.0]; ACTION_COUNT];
+        output[Action::Pass as usize] = vec![2.0, 5.0];
+        let network: Mlp = Mlp::new(vec![Dense::new(hidden, vec![-3.0, 3.0]).unwrap(),
+                                         Dense::new(output, vec![0.5; ACTION_COUNT]).unwrap()]).unwrap();
+
+        let logits: Vec<f32> = network.forward(&encode(&pinned_state()));
+        assert_eq!(logits.len(), ACTION_COUNT);
+        assert_eq!(logits[Action::Pass as usize], 4.5);
+        assert_eq!(logits[Action::HJPlay as usize], 2.5);
+    }
+
+    #[test]
+    fn illegal_actions_are_masked() {
+        let network: Mlp = constant_network(&[(Action::SAPlay, 9.0), (Action::CallH, 8.0), (Action::C9Play, 1.0), (Action::HJPlay, -1.0)]);
+        let state: ScopedGameState = pinned_state();
+        // Player 1 can't follow spades, so may play anything but the spade ace they don't hold
+        assert_eq!(MlpAgent::new(network.clone(), PolicyChoice::Greedy, None).unwrap().decide_action(&state), Action::C9Play);
+
+        let mut sampler: MlpAgent = MlpAgent::new(network.clone(), PolicyChoice::Sampled { temperature: 1.0 }, Some(3)).unwrap();
+        let mut again: MlpAgent = MlpAgent::new(network, PolicyChoice::Sampled { temperature: 1.0 }, Some(3)).unwrap();
+        let choices: Vec<Action> = (0..200).map(|_| sampler.decide_action(&state)).collect();
+        assert!(choices.iter().all(|x| state.legal_actions.contains(x)));
+        assert_eq!(choices, (0..200).map(|_| again.decide_action(&state)).collect::<Vec<Action>>());
+
+        // C9 is weighted e, HJ 1/e and the other three cards 1, so C9 is sampled about 45% of the time
+        let c9: usize = choices.iter().filter(|x| **x == Action::C9Play).count();
+        assert!((50..110).contains(&c9), "{}", c9);
+    }
+
+    #[test]
+    fn temperature_must_be_positive() {
+        let network: Mlp = constant_network(&[(Action::C9Play, 1.0)]);
+        for temperature in [0.0, -1.0, f32::NAN, f32::INFINITY] {
+            assert!(MlpAgent::new(network.clone(), PolicyChoice::Sampled { temperature }, None).is_err(), "{}", temperature);
+        }
+
+        // Infinite logits leave nothing to sample from, so the greedy choice is taken
+        let network: Mlp = constant_network(&[(Action::C9Play, f32::INFINITY), (Action::HJPlay, f32::INFINITY)]);
+        let mut greedy: MlpAgent = MlpAgent::new(network.clone(), PolicyChoice::Greedy, None).unwrap();
+        let mut sampler: MlpAgent = MlpAgent::new(network, PolicyChoice::Sampled { temperature: 1.0 }, Some(0)).unwrap();
+        assert_eq!(sampler.decide_action(&pinned_state()), greedy.decide_action(&pinned_state()));
+    }
+
+    #[test]
+    fn weights_file_round_trip() {
+        let network: Mlp = constant_network(&[(Action::Pass, 0.25), (Action::CallSAlone, -1.5)]);
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("euchre_rs_mlp_{}.txt", std::process::id()));
+        network.save(&path).unwrap();
+        let loaded: Mlp = Mlp::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, network);
+
+        let mut text: Vec<u8> = vec![];
+        network.write_to(&mut text).unwrap();
+        let text: String = String::from_utf8(text).unwrap().replacen("dense", "# a comment\n\ndense", 1);
+        assert_eq!(Mlp::read_from(text.as_bytes()).unwrap(), network);
+
+        let bad: [&str; 4] = ["euchre-mlp 2\n", "euchre-mlp 1\n", "euchre-mlp 1\ndense 194 59\n1 2\n", "euchre-mlp 1\ndense 2 1\n1 2\n0\n"];
+        for text in bad {
+            assert_eq!(Mlp::read_from(text.as_bytes()).unwrap_err().kind(), io::ErrorKind::InvalidData, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn layers_must_fit() {
+        assert!(Dense::new(vec![vec![1.0, 2.0], vec![1.0]], vec![0.0, 0.0]).is_err());
+        assert!(Dense::new(vec![vec![1.0, 2.0]], vec![0.0, 0.0]).is_err());
+        let first: Dense = Dense::new(vec![vec![0.0; FEATURE_LEN]; 3], vec![0.0; 3]).unwrap();
+        let last: Dense = Dense::new(vec![vec![0.0; 4]; ACTION_COUNT], vec![0.0; ACTION_COUNT]).unwrap();
+        assert!(Mlp::new(vec![]).is_err());
+        assert!(Mlp::new(vec![first.clone(), last]).is_err());
+        assert!(Mlp::new(vec![first]).is_err());
+    }
+}
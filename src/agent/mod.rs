@@ -1,9 +1,41 @@
 use crate::game::scoped_state::ScopedGameState;
 use crate::action::Action;
+use crate::card::Card;
 
 pub mod random;
 pub mod human;
+pub mod heuristic;
+pub mod determinize;
+pub mod pimc;
+pub mod ismcts;
+pub mod qlearning;
+pub mod mlp;
+pub mod scripted;
+#[cfg(feature = "json")]
+pub mod subprocess;
 
+/// A player of euchre.  
+/// `EuchreEnv` calls `decide_action` whenever it is the agent's turn,
+/// and the other hooks for every agent as the hand progresses, so agents can track the cards played.
+/// When a game is resumed after some moves, those moves are shown through the hooks before anyone decides.
+/// The hooks do nothing by default.
 pub trait Agent{
     fn decide_action(&mut self, state: &ScopedGameState) -> Action;
+
+    /// A new hand was dealt, and this agent sits in `seat` holding `hand`
+    fn on_hand_start(&mut self, _seat: u8, _hand: &[Card]) {}
+
+    /// The player in `seat` took `action`, including this agent.  
+    /// A dealer's discard is only shown to the dealer.
+    fn observe_action(&mut self, _seat: u8, _action: Action) {}
+
+    /// The player in `winner` won a trick of `cards`, in the order they were played
+    fn on_trick_end(&mut self, _winner: u8, _cards: &[Card]) {}
+
+    /// The hand is over and each seat earned `rewards[seat]` points
+    fn on_hand_end(&mut self, _rewards: &[u8]) {}
+
+    /// Restart the agent's random choices from `seed`, so the games it plays next can be repeated.
+    /// Agents that make no random choices ignore it
+    fn reseed(&mut self, _seed: u64) {}
 }
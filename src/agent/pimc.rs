@@ -0,0 +1,231 @@
+use rand_chacha::ChaCha8Rng;
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::agent::determinize::Determinizer;
+use crate::card::{Card, Suit};
+use crate::game::scoped_state::ScopedGameState;
+use crate::judger::Judger;
+use crate::solver::{self, Position, Solution};
+use crate::utils::get_rdm_gen;
+
+/// An agent using Perfect Information Monte Carlo: it deals the cards it can't see at random,
+/// consistent with what it knows (see `Determinizer`), solves each of those deals exactly,
+/// and takes the action with the best average result.
+///
+/// Every action is valued by the points its team scores minus the points the other team scores:
+/// 1. Playing a card is valued by solving the rest of the hand with `solver::solve`.
+/// 2. A discard is valued by solving the whole hand with that card thrown away.
+/// 3. Ordering up or calling is valued by solving the whole hand with that trump,
+///    the dealer throwing away their weakest card after picking up.
+///    Passing is valued at 0, as if nobody else would call.
+///
+/// Points are scored by the rules of the game being played, `state.rules`.
+pub struct PimcAgent {
+    samples: usize,
+    gen: ChaCha8Rng,
+}
+
+impl Agent for PimcAgent {
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        let legal: &Vec<Action> = &state.legal_actions;
+        if legal.len() == 1 {
+            return legal[0]
+        }
+
+        let determinizer: Determinizer = Determinizer::new(state);
+        let mut totals: Vec<i32> = vec![0; legal.len()];
+        for _ in 0..self.samples {
+            let hands: Vec<Vec<Card>> = determinizer.sample(&mut self.gen);
+            for (total, value) in totals.iter_mut().zip(self.values(state, &determinizer, hands)) {
+                *total += value;
+            }
+        }
+
+        // The first of the best actions, so ties are broken the same way every time
+        let best: i32 = *totals.iter().max().unwrap();
+        legal[totals.iter().position(|x| *x == best).unwrap()]
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.gen = get_rdm_gen(Some(seed));
+    }
+}
+
+impl PimcAgent {
+    /// Create an agent that solves `samples` deals per decision.
+    /// Panics if `samples` is 0.
+    pub fn new(samples: usize, seed: Option<u64>) -> Self {
+        if samples == 0 {
+            panic!("PimcAgent needs at least one sample");
+        }
+        PimcAgent {
+            samples,
+            gen: get_rdm_gen(seed),
+        }
+    }
+
+    /// Deals solved per decision.
+    pub fn samples(&self) -> usize {
+        self.samples
+    }
+
+    /// Value every legal action of `state` on one sampled deal, in the order of `state.legal_actions`.
+    fn values(&self, state: &ScopedGameState, determinizer: &Determinizer, hands: Vec<Vec<Card>>) -> Vec<i32> {
+        let me: u8 = state.current_actor;
+        let Some(trump) = state.trump else {
+            return state.legal_actions.iter().map(|x| self.bid_value(state, &hands, *x)).collect()
+        };
+        let calling: u8 = state.calling_actor.unwrap();
+        let alone: bool = state.alone_actor.is_some();
+
+        if state.legal_actions[0].is_discard() {
+            return state.legal_actions.iter().map(|action| {
+                let card: Card = Action::action_to_card(*action).unwrap();
+                let mut hands: Vec<Vec<Card>> = hands.clone();
+                hands[usize::from(me)].retain(|x| *x != card);
+                self.hand_value(state, calling, alone, trump, hands)
+            }).collect()
+        }
+
+        let position: Position = Position {
+            hands,
+            trump,
+            leader: state.order[0],
+            sitting_out: state.alone_actor.map(|x| (x + 2) % 4),
+            center: state.center.clone().unwrap_or_default(),
+        };
+        let solution: Solution = solver::solve(&position).unwrap();
+        let won: [u8; 2] = determinizer.tricks_won();
+        state.legal_actions.iter().map(|action| {
+            let card: Card = Action::action_to_card(*action).unwrap();
+            let (_, mine) = solution.card_values.iter().find(|(x, _)| *x == card).unwrap();
+            let remaining: u8 = solution.tricks[0] + solution.tricks[1];
+            let mut tricks: [u8; 2] = won;
+            tricks[usize::from(me % 2)] += mine;
+            tricks[usize::from(1 - me % 2)] += remaining - mine;
+            self.points(state, calling, alone, tricks)
+        }).collect()
+    }
+
+    /// Value a bidding action on a deal of `hands`.
+    fn bid_value(&self, state: &ScopedGameState, hands: &[Vec<Card>], action: Action) -> i32 {
+        let me: u8 = state.current_actor;
+        let dealer: usize = usize::from(state.dealer_actor);
+        let trump: Suit = match (action, action.called_suit()) {
+            (Action::Pick | Action::PickAlone, _) => state.flipped_card.suit(),
+            (_, Some(suit)) => suit,
+            _ => return 0,
+        };
+
+        let alone: bool = action.is_alone();
+        let mut hands: Vec<Vec<Card>> = hands.to_vec();
+        if alone {
+            hands[usize::from((me + 2) % 4)].clear();
+        }
+        if action.called_suit().is_none() && !hands[dealer].is_empty() {
+            hands[dealer].push(state.flipped_card);
+            let weakest: Card = weakest(&hands[dealer], trump);
+            hands[dealer].retain(|x| *x != weakest);
+        }
+        self.hand_value(state, me, alone, trump, hands)
+    }
+
+    /// Value a hand about to be played from its first trick, dealt by the dealer of `state`.
+    fn hand_value(&self, state: &ScopedGameState, calling: u8, alone: bool, trump: Suit, hands: Vec<Vec<Card>>) -> i32 {
+        let sitting_out: Option<u8> = if alone {Some((calling + 2) % 4)} else {None};
+        let leader: u8 = (1..=4).map(|x| (state.dealer_actor + x) % 4).find(|x| Some(*x) != sitting_out).unwrap();
+        let position: Position = Position { sitting_out, ..Position::new(hands, trump, leader) };
+        let solution: Solution = solver::solve(&position).unwrap();
+        self.points(state, calling, alone, solution.tricks)
+    }
+
+    /// Points scored by the current player's team minus the other team's, given the tricks each team took.
+    fn points(&self, state: &ScopedGameState, calling: u8, alone: bool, tricks: [u8; 2]) -> i32 {
+        let me: u8 = state.current_actor;
+        let points: Vec<u8> = Judger::with_rules(state.rules).judge_round(vec![tricks[0], tricks[1], 0, 0], calling, alone);
+        i32::from(points[usize::from(me)]) - i32::from(points[usize::from((me + 1) % 4)])
+    }
+}
+
+/// The card a dealer throws away after picking up: their lowest off-suit card, or lowest trump.
+fn weakest(hand: &[Card], trump: Suit) -> Card {
+    *hand.iter()
+        .min_by_key(|x| (x.effective_suit(trump) == trump, x.is_right(trump), x.is_left(trump), x.rank() as u8))
+        .unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::dealer::DealSpec;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+    use crate::game::EuchreGame;
+    use crate::rules::RuleSet;
+
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(|x| Card::from_str(x).unwrap()).collect()
+    }
+
+    #[test]
+    fn same_seed_same_choices() {
+        let mut game: EuchreGame = EuchreGame::new(Some(1), Some(3), RuleSet::default());
+        let mut first: PimcAgent = PimcAgent::new(3, Some(9));
+        let mut second: PimcAgent = PimcAgent::new(3, Some(9));
+        while !game.is_over() {
+            let state: ScopedGameState = game.get_state();
+            let action: Action = first.decide_action(&state);
+            assert_eq!(action, second.decide_action(&state));
+            assert!(state.legal_actions.contains(&action));
+            game.step(action).unwrap();
+        }
+    }
+
+    #[test]
+    fn orders_up_a_lock() {
+        // Player 1 holds the top five hearts, so ordering up alone marches every time
+        let spec: DealSpec = DealSpec::new().hand(1, &cards("HJ DJ HA HK HQ")).flipped(Card::from_str("H9").unwrap());
+        let game: EuchreGame = EuchreGame::with_deal_spec(Some(0), Some(5), RuleSet::default(), &spec).unwrap();
+        assert_eq!(PimcAgent::new(4, Some(1)).decide_action(&game.get_state()), Action::PickAlone);
+    }
+
+    #[test]
+    fn scores_by_the_games_rules() {
+        // The same lock, in a game where marching alone is worth less than marching with a partner
+        let rules: RuleSet = RuleSet { alone_march_points: 1, ..RuleSet::default() };
+        let spec: DealSpec = DealSpec::new().hand(1, &cards("HJ DJ HA HK HQ")).flipped(Card::from_str("H9").unwrap());
+        let game: EuchreGame = EuchreGame::with_deal_spec(Some(0), Some(5), rules, &spec).unwrap();
+        assert_eq!(PimcAgent::new(4, Some(1)).decide_action(&game.get_state()), Action::Pick);
+    }
+
+    #[test]
+    fn discards_the_off_suit_card() {
+        // Player 2 orders their partner up into four of the top hearts, so keeping the club nine risks the march
+        let spec: DealSpec = DealSpec::new().hand(0, &cards("HJ DJ HA HK C9")).flipped(Card::from_str("HQ").unwrap());
+        let moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pick)];
+        let game: EuchreGame = EuchreGame::from_moves(Some(0), Some(2), RuleSet::default(), &spec, &moves).unwrap();
+        assert_eq!(PimcAgent::new(4, Some(3)).decide_action(&game.get_state()), Action::C9Discard);
+    }
+
+    #[test]
+    fn beats_random_players() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(PimcAgent::new(2, Some(0))),
+                                                Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(PimcAgent::new(2, Some(2))),
+                                                Box::new(RandomAgent::new(Some(3)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, None, None, false));
+        let mut points: [u32; 2] = [0, 0];
+        for hand in 0..12u64 {
+            env.config.seed = Some(hand);
+            let rewards: Vec<u8> = env.run();
+            points[0] += u32::from(rewards[0]);
+            points[1] += u32::from(rewards[1]);
+        }
+        assert!(points[0] > points[1], "{:?}", points);
+    }
+}
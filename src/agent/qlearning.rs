@@ -0,0 +1,616 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::card::{Card, Rank, Suit};
+use crate::error::EuchreError;
+use crate::game::scoped_state::ScopedGameState;
+use crate::judger::Judger;
+use crate::utils::get_rdm_gen;
+
+/// First line of a saved `QTable`.
+const TABLE_HEADER: &str = "euchre-qtable 1";
+
+/// Which kind of decision a `QKey` is about.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Phase {
+    /// First round of bidding, on the flipped card
+    OrderUp,
+    /// Second round of bidding, naming a suit
+    Call,
+    Discard,
+    Lead,
+    Follow,
+}
+
+/// What a `QKey`'s action does, without naming cards or suits.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ActionKey {
+    Pass,
+    /// Order up or call, with a partner
+    Bid,
+    /// Order up or call alone
+    Alone,
+    /// Play or discard a card
+    Card {
+        trump: bool,
+        /// Position of the card within its suit once trump is known, 0 being the lowest.
+        /// Off-suit cards go from 0 for the nine to 5 for the ace, trump from 0 for the nine to 6 for the right bower.
+        rank: u8,
+        /// Whether the card would win the trick as it stands
+        wins: bool,
+    },
+}
+
+/// An abstracted view of taking an action, small enough to learn a table of values over.
+///
+/// Hand features are relative to the suit the decision is about:
+/// the flipped card's suit when ordering up, the suit named when calling
+/// (or the longest suit that could be named when passing), and trump once it's decided.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct QKey {
+    pub phase: Phase,
+    /// Seats left of the dealer, 0 being the dealer
+    pub seat: u8,
+    /// Cards held of the suit, counting the left bower
+    pub trumps: u8,
+    pub right: bool,
+    pub left: bool,
+    /// Aces held of the other suits
+    pub off_aces: u8,
+    /// Whether this player's team called trump
+    pub makers: bool,
+    /// Whether partner is winning the trick so far
+    pub partner_winning: bool,
+    pub action: ActionKey,
+}
+
+impl QKey {
+    /// The key for the current player of `state` taking `action`.
+    pub fn new(state: &ScopedGameState, action: Action) -> QKey {
+        let me: u8 = state.current_actor;
+        let center: Vec<Card> = state.center.clone().unwrap_or_default();
+        let phase: Phase = if action.is_play() {
+            if center.is_empty() {Phase::Lead} else {Phase::Follow}
+        } else if action.is_discard() {
+            Phase::Discard
+        } else if state.flipped_choice.is_none() {
+            Phase::OrderUp
+        } else {
+            Phase::Call
+        };
+
+        let trumps = |suit: Suit| state.hand.iter().filter(|x| x.effective_suit(suit) == suit).count();
+        let suit: Suit = match (phase, state.trump) {
+            (Phase::OrderUp, _) => state.flipped_card.suit(),
+            (_, Some(trump)) => trump,
+            _ => action.called_suit()
+                .or_else(|| state.legal_actions.iter().filter_map(|x| x.called_suit()).max_by_key(|x| trumps(*x)))
+                .unwrap_or(state.flipped_card.suit()),
+        };
+
+        // Who wins the trick so far, and who would win it with `card` added
+        let winner = |cards: &[Card]| Judger::new().judge_trick(suit, cards, &state.order[..cards.len()]);
+        let action_key: ActionKey = match Action::action_to_card(action) {
+            Ok(card) => ActionKey::Card {
+                trump: card.effective_suit(suit) == suit,
+                rank: Self::rank(card, suit),
+                wins: phase == Phase::Follow && winner(&[center.as_slice(), &[card]].concat()) == me,
+            },
+            Err(_) if action == Action::Pass => ActionKey::Pass,
+            Err(_) if action.is_alone() => ActionKey::Alone,
+            Err(_) => ActionKey::Bid,
+        };
+
+        QKey {
+            phase,
+            seat: (me + 4 - state.dealer_actor) % 4,
+            trumps: trumps(suit) as u8,
+            right: state.hand.iter().any(|x| x.is_right(suit)),
+            left: state.hand.iter().any(|x| x.is_left(suit)),
+            off_aces: state.hand.iter().filter(|x| x.rank() == Rank::Ace && x.suit() != suit).count() as u8,
+            makers: state.calling_actor.is_some_and(|x| x % 2 == me % 2),
+            partner_winning: phase == Phase::Follow && winner(&center) == (me + 2) % 4,
+            action: action_key,
+        }
+    }
+
+    /// Position of `card` within its suit when `trump` is trump, see `ActionKey::Card`.
+    fn rank(card: Card, trump: Suit) -> u8 {
+        if card.is_right(trump) {
+            6
+        } else if card.is_left(trump) {
+            5
+        } else {
+            let rank: u8 = card.rank() as u8 - 9;
+            // Take out the jack, which is a bower when trump
+            if card.suit() == trump && card.rank() as u8 > Rank::Jack as u8 {rank - 1} else {rank}
+        }
+    }
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name: &str = match self {
+            Phase::OrderUp => "order_up",
+            Phase::Call => "call",
+            Phase::Discard => "discard",
+            Phase::Lead => "lead",
+            Phase::Follow => "follow",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for ActionKey {
+    /// `pass`, `bid` or `alone`, or a card written as `t` for trump or `o` for off-suit,
+    /// its rank, then `w` if it wins the trick or `-` if not, e.g. `t6w`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ActionKey::Pass => write!(f, "pass"),
+            ActionKey::Bid => write!(f, "bid"),
+            ActionKey::Alone => write!(f, "alone"),
+            ActionKey::Card { trump, rank, wins } => {
+                write!(f, "{}{}{}", if *trump {'t'} else {'o'}, rank, if *wins {'w'} else {'-'})
+            },
+        }
+    }
+}
+
+impl fmt::Display for QKey {
+    /// Every field separated by spaces, in the order they're declared, with `true` and `false` written as 1 and 0,
+    /// e.g. `follow 2 3 1 0 1 1 0 t6w`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} {} {} {} {} {} {}", self.phase, self.seat, self.trumps, u8::from(self.right), u8::from(self.left),
+               self.off_aces, u8::from(self.makers), u8::from(self.partner_winning), self.action)
+    }
+}
+
+impl FromStr for QKey {
+    type Err = EuchreError;
+
+    /// Parse a key written the way it is displayed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || EuchreError::Parse(format!("{:?} is not a Q-table key", s));
+        let fields: Vec<&str> = s.split_whitespace().collect();
+        let [phase, seat, trumps, right, left, off_aces, makers, partner_winning, action] = fields[..] else {
+            return Err(err())
+        };
+        let number = |x: &str| x.parse::<u8>().map_err(|_| err());
+        let flag = |x: &str| match x {
+            "0" => Ok(false),
+            "1" => Ok(true),
+            _ => Err(err()),
+        };
+
+        let phase: Phase = match phase {
+            "order_up" => Phase::OrderUp,
+            "call" => Phase::Call,
+            "discard" => Phase::Discard,
+            "lead" => Phase::Lead,
+            "follow" => Phase::Follow,
+            _ => return Err(err()),
+        };
+        let action: ActionKey = match action.as_bytes() {
+            b"pass" => ActionKey::Pass,
+            b"bid" => ActionKey::Bid,
+            b"alone" => ActionKey::Alone,
+            [kind @ (b't' | b'o'), rank @ b'0'..=b'6', wins @ (b'w' | b'-')] => {
+                ActionKey::Card { trump: *kind == b't', rank: rank - b'0', wins: *wins == b'w' }
+            },
+            _ => return Err(err()),
+        };
+
+        Ok(QKey {
+            phase,
+            seat: number(seat)?,
+            trumps: number(trumps)?,
+            right: flag(right)?,
+            left: flag(left)?,
+            off_aces: number(off_aces)?,
+            makers: flag(makers)?,
+            partner_winning: flag(partner_winning)?,
+            action,
+        })
+    }
+}
+
+/// The learned value of every `QKey` seen so far; unseen keys are worth 0.
+///
+/// Saved as text: a `euchre-qtable 1` header line, then one line per key
+/// holding the key as it is displayed, a tab, and its value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QTable {
+    values: HashMap<QKey, f64>,
+}
+
+impl QTable {
+    pub fn new() -> QTable {
+        QTable { values: HashMap::new() }
+    }
+
+    /// The value of `key`, 0 if it hasn't been learned.
+    pub fn get(&self, key: &QKey) -> f64 {
+        self.values.get(key).copied().unwrap_or(0.0)
+    }
+
+    pub fn set(&mut self, key: QKey, value: f64) {
+        self.values.insert(key, value);
+    }
+
+    /// Number of keys with a learned value.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Save the table to the file at `path`, replacing it if it exists.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer: BufWriter<File> = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Load a table saved with `save`.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<QTable> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Write the table in its saved format, keys sorted so equal tables are written identically.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut lines: Vec<String> = self.values.iter().map(|(key, value)| format!("{}\t{}", key, value)).collect();
+        lines.sort();
+        writeln!(writer, "{}", TABLE_HEADER)?;
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Read a table in its saved format.
+    /// Errors with `InvalidData` if the header is missing or a line can't be parsed.
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<QTable> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(TABLE_HEADER) {
+            return Err(invalid(format!("Q-table must start with {:?}", TABLE_HEADER)))
+        }
+
+        let mut table: QTable = QTable::new();
+        for line in lines {
+            let line: String = line?;
+            let (key, value) = line.split_once('\t').ok_or_else(|| invalid(format!("{:?} has no value", line)))?;
+            let key: QKey = key.parse().map_err(|e: EuchreError| invalid(e.to_string()))?;
+            let value: f64 = value.parse().map_err(|_| invalid(format!("{:?} is not a number", value)))?;
+            table.set(key, value);
+        }
+        Ok(table)
+    }
+}
+
+/// How a `QLearningAgent` explores and learns.
+/// Change the defaults with struct update syntax:
+///
+/// ```
+/// use euchre_rs::agent::qlearning::{QLearningAgent, QLearningConfig};
+///
+/// // Play greedily without learning, e.g. to evaluate a trained table
+/// let agent = QLearningAgent::with_config(QLearningConfig { epsilon: 0.0, learning_rate: 0.0, ..QLearningConfig::default() }, Some(3)).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QLearningConfig {
+    /// How far each value moves toward its target after a hand, between 0 and 1
+    pub learning_rate: f64,
+    /// How much the value of the next decision counts toward the value of this one
+    pub discount: f64,
+    /// Chance of taking a random legal action instead of the best known one, between 0 and 1
+    pub epsilon: f64,
+}
+
+impl QLearningConfig {
+    /// Returns an error if `learning_rate` or `epsilon` isn't between 0 and 1.
+    pub fn validate(&self) -> Result<(), EuchreError> {
+        for (name, value) in [("learning_rate", self.learning_rate), ("epsilon", self.epsilon)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(EuchreError::InvalidAgentConfig(format!("{} must be between 0 and 1, got {}", name, value)))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for QLearningConfig {
+    fn default() -> Self {
+        QLearningConfig {
+            learning_rate: 0.1,
+            discount: 1.0,
+            epsilon: 0.1,
+        }
+    }
+}
+
+/// One decision made during a hand.
+struct Step {
+    seat: u8,
+    chosen: QKey,
+    /// The keys of every legal action at the time
+    options: Vec<QKey>,
+}
+
+/// A tabular Q-learning agent.
+///
+/// Decisions are looked up in a `QTable` by their `QKey`, taking the best valued legal action,
+/// or a random one with chance `epsilon`. Decisions with a single legal action aren't learned.
+/// When `EuchreEnv` reports the end of a hand through `on_hand_end`, every decision of the hand
+/// is updated, last to first, toward the discounted value of the best action at the next decision,
+/// or for the last decision toward the hand's reward:
+/// the points this agent's team won minus the other team's.
+pub struct QLearningAgent {
+    config: QLearningConfig,
+    table: QTable,
+    steps: Vec<Step>,
+    gen: ChaCha8Rng,
+}
+
+impl Agent for QLearningAgent {
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        let legal: &Vec<Action> = &state.legal_actions;
+        if legal.len() == 1 {
+            return legal[0]
+        }
+
+        let options: Vec<QKey> = legal.iter().map(|x| QKey::new(state, *x)).collect();
+        let choice: usize = if self.gen.gen_bool(self.config.epsilon) {
+            self.gen.gen_range(0..legal.len())
+        } else {
+            self.best(&options)
+        };
+        self.steps.push(Step { seat: state.current_actor, chosen: options[choice], options });
+        legal[choice]
+    }
+
+    fn on_hand_start(&mut self, _seat: u8, _hand: &[Card]) {
+        self.steps.clear();
+    }
+
+    fn on_hand_end(&mut self, rewards: &[u8]) {
+        let steps: Vec<Step> = std::mem::take(&mut self.steps);
+        let Some(seat) = steps.first().map(|x| usize::from(x.seat)) else {
+            return
+        };
+        let reward: f64 = f64::from(rewards[seat]) - f64::from(rewards[(seat + 1) % 4]);
+
+        let mut target: f64 = reward;
+        for step in steps.iter().rev() {
+            let value: f64 = self.table.get(&step.chosen);
+            self.table.set(step.chosen, value + self.config.learning_rate * (target - value));
+            target = self.config.discount * self.table.get(&step.options[self.best(&step.options)]);
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.gen = get_rdm_gen(Some(seed));
+    }
+}
+
+impl QLearningAgent {
+    /// Create an agent with an empty table and the default config.
+    pub fn new(seed: Option<u64>) -> Self {
+        Self::with_config(QLearningConfig::default(), seed).unwrap()
+    }
+
+    /// Create an agent with an empty table.  
+    /// Returns an error if the config is invalid, see `QLearningConfig::validate`.
+    pub fn with_config(config: QLearningConfig, seed: Option<u64>) -> Result<Self, EuchreError> {
+        config.validate()?;
+        Ok(QLearningAgent {
+            config,
+            table: QTable::new(),
+            steps: vec![],
+            gen: get_rdm_gen(seed),
+        })
+    }
+
+    /// Start from `table`, e.g. one loaded with `QTable::load`, instead of an empty table.
+    pub fn with_table(mut self, table: QTable) -> Self {
+        self.table = table;
+        self
+    }
+
+    /// The config this agent explores and learns with.
+    pub fn config(&self) -> QLearningConfig {
+        self.config
+    }
+
+    /// Change how the agent explores and learns, e.g. to stop exploring once trained.  
+    /// Returns an error and keeps the current config if `config` is invalid, see `QLearningConfig::validate`.
+    pub fn set_config(&mut self, config: QLearningConfig) -> Result<(), EuchreError> {
+        config.validate()?;
+        self.config = config;
+        Ok(())
+    }
+
+    /// The values learned so far, e.g. to `save` them.
+    pub fn table(&self) -> &QTable {
+        &self.table
+    }
+
+    pub fn into_table(self) -> QTable {
+        self.table
+    }
+
+    /// Index of the best valued option, the first if several tie.
+    fn best(&self, options: &[QKey]) -> usize {
+        let values: Vec<f64> = options.iter().map(|x| self.table.get(x)).collect();
+        let most: f64 = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        values.iter().position(|x| *x == most).unwrap()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+    use crate::game::EuchreGame;
+    use crate::rules::RuleSet;
+
+    #[test]
+    fn keys_round_trip() {
+        let mut agent: RandomAgent = RandomAgent::new(Some(2));
+        for seed in 0..10 {
+            let mut game: EuchreGame = EuchreGame::new(None, Some(seed), RuleSet::default());
+            while !game.is_over() {
+                let state: ScopedGameState = game.get_state();
+                for action in &state.legal_actions {
+                    let key: QKey = QKey::new(&state, *action);
+                    assert_eq!(key.to_string().parse::<QKey>().unwrap(), key);
+                }
+                game.step(agent.decide_action(&state)).unwrap();
+            }
+        }
+        assert!("follow 2 3 1 0 1 1 0".parse::<QKey>().is_err());
+        assert!("follow 2 3 1 0 1 1 2 t6w".parse::<QKey>().is_err());
+        assert!("follow 2 3 1 0 1 1 0 t7w".parse::<QKey>().is_err());
+    }
+
+    #[test]
+    fn card_keys() {
+        // Player 0 leads the spade ace, player 1 can't follow and holds the right bower of hearts
+        let spec: crate::dealer::DealSpec = crate::dealer::DealSpec::new()
+            .hand(0, &[Card::new(Suit::Spades, Rank::Ace)])
+            .hand(1, &[Card::new(Suit::Hearts, Rank::Jack), Card::new(Suit::Diamonds, Rank::Jack), Card::new(Suit::Hearts, Rank::Ten),
+                       Card::new(Suit::Clubs, Rank::Ace), Card::new(Suit::Diamonds, Rank::Nine)])
+            .flipped(Card::new(Suit::Hearts, Rank::Nine));
+        let moves: Vec<(u8, Action)> = vec![(0, Action::Pass), (1, Action::Pass), (2, Action::Pass), (3, Action::Pick),
+                                            (3, Action::H9Discard), (0, Action::SAPlay)];
+        let game: EuchreGame = EuchreGame::from_moves(Some(3), Some(1), RuleSet::default(), &spec, &moves).unwrap();
+        let state: ScopedGameState = game.get_state();
+
+        let key: QKey = QKey::new(&state, Action::HJPlay);
+        assert_eq!(key.to_string(), "follow 2 3 1 1 1 1 0 t6w");
+        assert_eq!(QKey::new(&state, Action::DJPlay).action, ActionKey::Card { trump: true, rank: 5, wins: true });
+        assert_eq!(QKey::new(&state, Action::D9Play).action, ActionKey::Card { trump: false, rank: 0, wins: false });
+    }
+
+    #[test]
+    fn learns_hand_rewards() {
+        let config: QLearningConfig = QLearningConfig { epsilon: 0.0, learning_rate: 0.5, ..QLearningConfig::default() };
+        let mut agent: QLearningAgent = QLearningAgent::with_config(config, Some(0)).unwrap();
+        let game: EuchreGame = EuchreGame::new(Some(0), Some(3), RuleSet::default());
+        let state: ScopedGameState = game.get_state();
+
+        let action: Action = agent.decide_action(&state);
+        let key: QKey = QKey::new(&state, action);
+        agent.on_hand_end(&[0, 2, 0, 2]);
+        assert_eq!(agent.table().get(&key), 1.0);
+        assert_eq!(agent.table().len(), 1);
+
+        // Nothing is left to learn from once the hand's decisions are used
+        agent.on_hand_end(&[0, 2, 0, 2]);
+        assert_eq!(agent.table().get(&key), 1.0);
+    }
+
+    #[test]
+    fn config_must_be_in_range() {
+        let config: QLearningConfig = QLearningConfig { epsilon: 1.5, ..QLearningConfig::default() };
+        assert_eq!(QLearningAgent::with_config(config, None).err().map(|e| e.to_string()),
+                   Some(String::from("epsilon must be between 0 and 1, got 1.5")));
+        let config: QLearningConfig = QLearningConfig { learning_rate: f64::NAN, ..QLearningConfig::default() };
+        assert!(QLearningAgent::with_config(config, None).is_err());
+
+        let mut agent: QLearningAgent = QLearningAgent::new(None);
+        assert!(agent.set_config(QLearningConfig { epsilon: -0.1, ..QLearningConfig::default() }).is_err());
+        assert_eq!(agent.config(), QLearningConfig::default());
+        let greedy: QLearningConfig = QLearningConfig { epsilon: 0.0, ..QLearningConfig::default() };
+        agent.set_config(greedy).unwrap();
+        assert_eq!(agent.config(), greedy);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let mut table: QTable = QTable::new();
+        let game: EuchreGame = EuchreGame::new(Some(2), Some(5), RuleSet::default());
+        let state: ScopedGameState = game.get_state();
+        for (i, action) in state.legal_actions.iter().enumerate() {
+            table.set(QKey::new(&state, *action), i as f64 / 3.0 - 0.25);
+        }
+
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("euchre_rs_qtable_{}.txt", std::process::id()));
+        table.save(&path).unwrap();
+        let loaded: QTable = QTable::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, table);
+
+        let error: io::Error = QTable::read_from("not a table\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error: io::Error = QTable::read_from("euchre-qtable 1\nlead 0 1 0 0 0 0 0 o5-\tabc\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// Lets a test keep hold of an agent it gives to an `EuchreEnv`.
+    struct Shared(Rc<RefCell<QLearningAgent>>);
+
+    impl Agent for Shared {
+        fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+            self.0.borrow_mut().decide_action(state)
+        }
+
+        fn on_hand_start(&mut self, seat: u8, hand: &[Card]) {
+            self.0.borrow_mut().on_hand_start(seat, hand);
+        }
+
+        fn on_hand_end(&mut self, rewards: &[u8]) {
+            self.0.borrow_mut().on_hand_end(rewards);
+        }
+    }
+
+    fn team_points(players: Vec<Box<dyn Agent>>, hands: std::ops::Range<u64>) -> [u32; 2] {
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, None, None, false));
+        let mut points: [u32; 2] = [0, 0];
+        for hand in hands {
+            env.config.seed = Some(hand);
+            let rewards: Vec<u8> = env.run();
+            points[0] += u32::from(rewards[0]);
+            points[1] += u32::from(rewards[1]);
+        }
+        points
+    }
+
+    #[test]
+    fn trained_agents_beat_random_players() {
+        let learner: Rc<RefCell<QLearningAgent>> = Rc::new(RefCell::new(QLearningAgent::new(Some(0))));
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(Shared(Rc::clone(&learner))),
+                                                Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3)))];
+        team_points(players, 0..5000);
+
+        let table: QTable = learner.borrow().table().clone();
+        assert!(table.len() > 100);
+        let greedy: QLearningConfig = QLearningConfig { epsilon: 0.0, learning_rate: 0.0, ..QLearningConfig::default() };
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(QLearningAgent::with_config(greedy, Some(4)).unwrap().with_table(table.clone())),
+                                                Box::new(RandomAgent::new(Some(5))),
+                                                Box::new(QLearningAgent::with_config(greedy, Some(6)).unwrap().with_table(table)),
+                                                Box::new(RandomAgent::new(Some(7)))];
+        let points: [u32; 2] = team_points(players, 10_000..10_400);
+        assert!(4 * points[0] > 5 * points[1], "{:?}", points);
+    }
+}
@@ -15,7 +15,11 @@ impl Agent for RandomAgent {
     fn decide_action(&mut self, state: &ScopedGameState) -> Action {
         let actions: &Vec<Action> = &state.legal_actions;
 
-        actions.choose(&mut self.gen).unwrap().clone()
+        *actions.choose(&mut self.gen).unwrap()
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        self.gen = get_rdm_gen(Some(seed));
     }
 }
 
@@ -0,0 +1,261 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::card::Card;
+use crate::error::EuchreError;
+use crate::game::scoped_state::ScopedGameState;
+
+/// Why a `ScriptedAgent` couldn't take the next action of its script.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScriptError {
+    /// Every action of the script has been taken
+    Exhausted {
+        length: usize,
+    },
+    /// The next action of the script isn't legal in the state it was asked to act in
+    Illegal {
+        /// Position of the action in the script, counting from 0
+        index: usize,
+        action: Action,
+        legal_actions: Vec<Action>,
+    },
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScriptError::Exhausted { length } => write!(f, "The script ran out after all {} of its actions", length),
+            ScriptError::Illegal { index, action, legal_actions } => {
+                write!(f, "Action {} of the script, {}, is not one of the legal actions {:?}", index, action, legal_actions)
+            },
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+/// An agent that takes a fixed list of actions in order, for deterministic tests and replays.
+///
+/// Every action is checked against `state.legal_actions` before it is returned.
+/// When the script is exhausted or its next action is illegal, the agent panics with a `ScriptError`,
+/// unless it was given a fallback agent with `with_fallback`, which then decides instead.
+/// An illegal action is used up either way.
+/// Every other hook is passed on to the fallback agent, so it can keep track of the hand.
+///
+/// ### Example
+///
+/// ```
+/// use euchre_rs::action::Action;
+/// use euchre_rs::agent::random::RandomAgent;
+/// use euchre_rs::agent::scripted::ScriptedAgent;
+///
+/// let strict = ScriptedAgent::new(vec![Action::Pass, Action::CallH]);
+/// let lenient = ScriptedAgent::from_script("pass, CallH").unwrap().with_fallback(Box::new(RandomAgent::new(Some(2))));
+/// ```
+pub struct ScriptedAgent {
+    script: Vec<Action>,
+    next: usize,
+    fallback: Option<Box<dyn Agent>>,
+}
+
+impl Agent for ScriptedAgent {
+    /// Panics with the `ScriptError` if the script can't be followed and there is no fallback agent.
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        match (self.try_decide(state), &mut self.fallback) {
+            (Ok(action), _) => action,
+            (Err(_), Some(fallback)) => fallback.decide_action(state),
+            (Err(e), None) => panic!("{}", e),
+        }
+    }
+
+    fn on_hand_start(&mut self, seat: u8, hand: &[Card]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_start(seat, hand);
+        }
+    }
+
+    fn observe_action(&mut self, seat: u8, action: Action) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.observe_action(seat, action);
+        }
+    }
+
+    fn on_trick_end(&mut self, winner: u8, cards: &[Card]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_trick_end(winner, cards);
+        }
+    }
+
+    fn on_hand_end(&mut self, rewards: &[u8]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_end(rewards);
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.reseed(seed);
+        }
+    }
+}
+
+impl ScriptedAgent {
+    /// Create an agent that takes `script` in order.
+    pub fn new(script: Vec<Action>) -> Self {
+        ScriptedAgent {
+            script,
+            next: 0,
+            fallback: None,
+        }
+    }
+
+    /// Create an agent from actions written the way they are displayed, e.g. `"Pass Pick HJPlay"`.
+    /// Actions are separated by whitespace or commas, and case is ignored.
+    /// Returns a parse error naming the first word that isn't an action.
+    pub fn from_script(script: &str) -> Result<Self, EuchreError> {
+        let actions: Vec<Action> = script.split(|x: char| x.is_whitespace() || x == ',')
+            .filter(|x| !x.is_empty())
+            .map(|x| Action::from_str(x).map_err(|_| EuchreError::Parse(format!("{:?} is not an action", x))))
+            .collect::<Result<Vec<Action>, EuchreError>>()?;
+        Ok(Self::new(actions))
+    }
+
+    /// Let `fallback` decide whenever the script can't be followed, instead of panicking.
+    pub fn with_fallback(mut self, fallback: Box<dyn Agent>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// Take the next action of the script if it is legal in `state`, without consulting the fallback agent.
+    pub fn try_decide(&mut self, state: &ScopedGameState) -> Result<Action, ScriptError> {
+        let Some(action) = self.script.get(self.next).copied() else {
+            return Err(ScriptError::Exhausted { length: self.script.len() })
+        };
+        self.next += 1;
+        if !state.legal_actions.contains(&action) {
+            return Err(ScriptError::Illegal { index: self.next - 1, action, legal_actions: state.legal_actions.clone() })
+        }
+        Ok(action)
+    }
+
+    /// The actions of the script not yet taken.
+    pub fn remaining(&self) -> &[Action] {
+        &self.script[self.next..]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+    use crate::game::EuchreGame;
+    use crate::rules::RuleSet;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn parses_scripts() {
+        let agent: ScriptedAgent = ScriptedAgent::from_script("pass,  PICK\nHJPlay,s9discard").unwrap();
+        assert_eq!(agent.remaining(), &[Action::Pass, Action::Pick, Action::HJPlay, Action::S9Discard]);
+        assert_eq!(ScriptedAgent::from_script("").unwrap().remaining(), &[]);
+        assert_eq!(ScriptedAgent::from_script("Pass Jump").err(), Some(EuchreError::Parse(String::from("\"Jump\" is not an action"))));
+    }
+
+    #[test]
+    fn replays_a_hand() {
+        // Everyone passes the flipped card, then player 0 calls spades, or hearts if spades were turned down.
+        // The random fallbacks play out the hand
+        let game: EuchreGame = EuchreGame::new(Some(3), Some(EuchreEnv::seed_for_hand(4, 0)), RuleSet::default());
+        let flipped: Action = Action::call(game.get_state().flipped_card.suit(), false).unwrap();
+        let call: Action = if flipped == Action::CallS {Action::CallH} else {Action::CallS};
+        let players: Vec<Box<dyn Agent>> = vec![
+            Box::new(ScriptedAgent::new(vec![Action::Pass, call]).with_fallback(Box::new(RandomAgent::new(Some(0))))),
+            Box::new(ScriptedAgent::new(vec![Action::Pass]).with_fallback(Box::new(RandomAgent::new(Some(1))))),
+            Box::new(ScriptedAgent::new(vec![Action::Pass]).with_fallback(Box::new(RandomAgent::new(Some(2))))),
+            Box::new(ScriptedAgent::new(vec![Action::Pass]).with_fallback(Box::new(RandomAgent::new(Some(3))))),
+        ];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(3), Some(4), false));
+        env.run();
+
+        let history: &[(u8, Action)] = env.get_action_history();
+        assert_eq!(history[..5], [(0, Action::Pass), (1, Action::Pass), (2, Action::Pass), (3, Action::Pass), (0, call)]);
+        assert!(env.game.is_over());
+    }
+
+    #[test]
+    fn reports_errors() {
+        let state: ScopedGameState = EuchreGame::new(Some(3), Some(4), RuleSet::default()).get_state();
+        let mut agent: ScriptedAgent = ScriptedAgent::new(vec![Action::CallH, Action::Pick]);
+
+        assert_eq!(agent.try_decide(&state), Err(ScriptError::Illegal { index: 0, action: Action::CallH, legal_actions: state.legal_actions.clone() }));
+        assert_eq!(agent.try_decide(&state), Ok(Action::Pick));
+        assert_eq!(agent.try_decide(&state), Err(ScriptError::Exhausted { length: 2 }));
+        assert_eq!(ScriptError::Exhausted { length: 2 }.to_string(), "The script ran out after all 2 of its actions");
+    }
+
+    #[test]
+    #[should_panic(expected = "Action 0 of the script, CallH, is not one of the legal actions")]
+    fn panics_without_fallback() {
+        let state: ScopedGameState = EuchreGame::new(Some(3), Some(4), RuleSet::default()).get_state();
+        ScriptedAgent::new(vec![Action::CallH]).decide_action(&state);
+    }
+
+    /// Plays randomly, counting every hook it is shown.
+    struct HookCounter {
+        inner: RandomAgent,
+        hooks: Rc<RefCell<usize>>,
+    }
+
+    impl Agent for HookCounter {
+        fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+            self.inner.decide_action(state)
+        }
+
+        fn on_hand_start(&mut self, _seat: u8, _hand: &[Card]) {
+            *self.hooks.borrow_mut() += 1;
+        }
+
+        fn observe_action(&mut self, _seat: u8, _action: Action) {
+            *self.hooks.borrow_mut() += 1;
+        }
+
+        fn on_trick_end(&mut self, _winner: u8, _cards: &[Card]) {
+            *self.hooks.borrow_mut() += 1;
+        }
+
+        fn on_hand_end(&mut self, _rewards: &[u8]) {
+            *self.hooks.borrow_mut() += 1;
+        }
+    }
+
+    #[test]
+    fn fallback_sees_every_hook() {
+        let hooks: Vec<Rc<RefCell<usize>>> = (0..4).map(|_| Rc::new(RefCell::new(0))).collect();
+        let players: Vec<Box<dyn Agent>> = (0..4).map(|x| Box::new(ScriptedAgent::new(vec![]).with_fallback(Box::new(HookCounter {
+            inner: RandomAgent::new(Some(x as u64)),
+            hooks: Rc::clone(&hooks[x]),
+        }))) as Box<dyn Agent>).collect();
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(0), Some(2), false));
+        env.run();
+
+        // The hand's start and end, every trick, and every action but the discard the others don't see
+        let actions: usize = env.get_action_history().len();
+        let discarded: bool = env.get_action_history().iter().any(|(_, x)| x.is_discard());
+        let expected: usize = 2 + env.game.get_tricks().len() + actions - usize::from(discarded);
+        assert!(hooks.iter().all(|x| *x.borrow() >= expected), "{:?}", hooks);
+        assert_eq!(hooks.iter().map(|x| *x.borrow()).sum::<usize>(), 4 * expected + usize::from(discarded));
+    }
+
+    #[test]
+    fn falls_back_when_exhausted() {
+        let state: ScopedGameState = EuchreGame::new(Some(3), Some(4), RuleSet::default()).get_state();
+        let mut agent: ScriptedAgent = ScriptedAgent::new(vec![]).with_fallback(Box::new(ScriptedAgent::new(vec![Action::Pass])));
+        assert_eq!(agent.decide_action(&state), Action::Pass);
+    }
+}
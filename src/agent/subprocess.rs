@@ -0,0 +1,432 @@
+//! Bots running in another process, talking line-delimited JSON over stdin and stdout.
+//!
+//! Every message is a single line holding a JSON object with a `type`.
+//! Cards and actions are written the way they are displayed, e.g. `"HJ"` and `"HJPlay"`.
+//!
+//! 1. Handshake: the bot is sent `{"type":"hello","protocol":1}`
+//!    and must answer `{"type":"ready"}`, optionally with a `"name"`.
+//! 2. Every time the bot must act it is sent `{"type":"state","state":{...}}`,
+//!    where the state is the bot's `ScopedGameState`, including its `legal_actions`.
+//!    It must answer `{"type":"action","action":"Pick"}` with one of the legal actions.
+//! 3. When the agent is dropped the bot is sent `{"type":"bye"}` and its stdin is closed.
+//!    The bot is killed if it hasn't exited within its `shutdown_grace`.
+//!
+//! Anything the bot writes to stderr is passed through to this process's stderr.
+//!
+//! A reply that isn't legal only costs the bot that move. A bot that exits, writes something
+//! that isn't a message, or doesn't read or answer a message in time is considered dead and is never asked again.
+//! Either way, the move goes to the fallback agent if there is one, see `SubprocessAgent::with_fallback`.
+
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::card::Card;
+use crate::game::scoped_state::ScopedGameState;
+
+/// Version of the protocol sent in the handshake.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How long a `SubprocessAgent` waits for its bot.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BotConfig {
+    /// Time allowed to answer the handshake, including starting up
+    pub handshake_timeout: Duration,
+    /// Time allowed to read and answer each state
+    pub move_timeout: Duration,
+    /// Time allowed to exit after `bye` before being killed
+    pub shutdown_grace: Duration,
+}
+
+impl Default for BotConfig {
+    fn default() -> Self {
+        BotConfig {
+            handshake_timeout: Duration::from_secs(5),
+            move_timeout: Duration::from_secs(1),
+            shutdown_grace: Duration::from_millis(500),
+        }
+    }
+}
+
+/// What went wrong talking to a bot.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BotError {
+    /// The bot's process could not be started
+    Spawn(String),
+    /// The bot didn't read or answer a message within the time allowed
+    Timeout(Duration),
+    /// The bot exited, or closed its stdin or stdout
+    Exited,
+    /// The bot wrote a line that isn't the message expected
+    BadReply(String),
+    /// The bot chose an action that isn't legal
+    Illegal {
+        action: Action,
+        legal_actions: Vec<Action>,
+    },
+    /// An earlier error left the bot dead, so it wasn't asked
+    Dead,
+}
+
+impl fmt::Display for BotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BotError::Spawn(reason) => write!(f, "Could not start the bot: {}", reason),
+            BotError::Timeout(limit) => write!(f, "The bot didn't read or answer a message within {:?}", limit),
+            BotError::Exited => write!(f, "The bot exited"),
+            BotError::BadReply(line) => write!(f, "The bot sent an unexpected reply: {:?}", line),
+            BotError::Illegal { action, legal_actions } => {
+                write!(f, "The bot chose {}, which is not one of the legal actions {:?}", action, legal_actions)
+            },
+            BotError::Dead => write!(f, "The bot stopped responding earlier in the game"),
+        }
+    }
+}
+
+impl std::error::Error for BotError {}
+
+/// Messages sent to the bot.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request<'a> {
+    Hello { protocol: u32 },
+    State { state: &'a ScopedGameState },
+    Bye,
+}
+
+/// Messages read from the bot.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Reply {
+    Ready {
+        #[serde(default)]
+        name: Option<String>,
+    },
+    Action { action: Action },
+}
+
+/// An agent that asks a bot in a child process for its actions, see the module docs for the protocol.
+pub struct SubprocessAgent {
+    child: Child,
+    /// Lines to write to the bot, written on their own thread so writes can time out
+    writer: Option<Sender<String>>,
+    /// Whether each line sent to `writer` was written
+    written: Receiver<io::Result<()>>,
+    /// Lines the bot writes, read on their own thread so reads can time out
+    lines: Receiver<io::Result<String>>,
+    config: BotConfig,
+    name: Option<String>,
+    dead: bool,
+    fallback: Option<Box<dyn Agent>>,
+}
+
+impl Agent for SubprocessAgent {
+    /// Panics with the `BotError` if the bot fails to choose a legal action and there is no fallback agent.
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        match (self.try_decide(state), &mut self.fallback) {
+            (Ok(action), _) => action,
+            (Err(_), Some(fallback)) => fallback.decide_action(state),
+            (Err(e), None) => panic!("{}", e),
+        }
+    }
+
+    fn on_hand_start(&mut self, seat: u8, hand: &[Card]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_start(seat, hand);
+        }
+    }
+
+    fn observe_action(&mut self, seat: u8, action: Action) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.observe_action(seat, action);
+        }
+    }
+
+    fn on_trick_end(&mut self, winner: u8, cards: &[Card]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_trick_end(winner, cards);
+        }
+    }
+
+    fn on_hand_end(&mut self, rewards: &[u8]) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_end(rewards);
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.reseed(seed);
+        }
+    }
+}
+
+impl SubprocessAgent {
+    /// Start `command` as a bot and complete the handshake.
+    /// The command's stdin and stdout are replaced with pipes to this agent.
+    /// Errors if the bot can't be started or doesn't answer the handshake in time.
+    pub fn spawn(mut command: Command, config: BotConfig) -> Result<Self, BotError> {
+        let mut child: Child = command.stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| BotError::Spawn(e.to_string()))?;
+        let mut stdin: ChildStdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        let (writer, to_write) = mpsc::channel::<String>();
+        let (done, written) = mpsc::channel();
+        thread::spawn(move || {
+            for line in to_write {
+                let result: io::Result<()> = writeln!(stdin, "{}", line).and_then(|_| stdin.flush());
+                let failed: bool = result.is_err();
+                if done.send(result).is_err() || failed {
+                    break
+                }
+            }
+        });
+
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if sender.send(line).is_err() {
+                    break
+                }
+            }
+        });
+
+        let mut agent: SubprocessAgent = SubprocessAgent {
+            child,
+            writer: Some(writer),
+            written,
+            lines,
+            config,
+            name: None,
+            dead: false,
+            fallback: None,
+        };
+        agent.send(&Request::Hello { protocol: PROTOCOL_VERSION }, config.handshake_timeout)?;
+        match agent.receive(config.handshake_timeout)? {
+            Reply::Ready { name } => agent.name = name,
+            Reply::Action { .. } => return Err(BotError::BadReply(String::from("an action before the handshake"))),
+        }
+        Ok(agent)
+    }
+
+    /// Let `fallback` decide whenever the bot fails to, instead of panicking.
+    pub fn with_fallback(mut self, fallback: Box<dyn Agent>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    /// The name the bot gave in the handshake, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Whether an earlier error means the bot won't be asked again.
+    pub fn is_dead(&self) -> bool {
+        self.dead
+    }
+
+    /// Ask the bot for its action in `state`, without consulting the fallback agent.
+    pub fn try_decide(&mut self, state: &ScopedGameState) -> Result<Action, BotError> {
+        if self.dead {
+            return Err(BotError::Dead)
+        }
+        self.send(&Request::State { state }, self.config.move_timeout)?;
+        let action: Action = match self.receive(self.config.move_timeout)? {
+            Reply::Action { action } => action,
+            Reply::Ready { .. } => return Err(self.kill(BotError::BadReply(String::from("a second handshake")))),
+        };
+        if !state.legal_actions.contains(&action) {
+            return Err(BotError::Illegal { action, legal_actions: state.legal_actions.clone() })
+        }
+        Ok(action)
+    }
+
+    /// Write one message to the bot, killing it if it can't be written within `timeout`.
+    fn send(&mut self, request: &Request, timeout: Duration) -> Result<(), BotError> {
+        let line: String = serde_json::to_string(request).expect("Requests always serialize");
+        let writer: &Sender<String> = self.writer.as_ref().ok_or(BotError::Exited)?;
+        if writer.send(line).is_err() {
+            return Err(self.kill(BotError::Exited))
+        }
+        match self.written.recv_timeout(timeout) {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => Err(self.kill(BotError::Exited)),
+            Err(RecvTimeoutError::Timeout) => Err(self.kill(BotError::Timeout(timeout))),
+        }
+    }
+
+    /// Read one message from the bot, killing it if none arrives within `timeout` or it can't be parsed.
+    fn receive(&mut self, timeout: Duration) -> Result<Reply, BotError> {
+        let line: String = match self.lines.recv_timeout(timeout) {
+            Ok(Ok(line)) => line,
+            Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => return Err(self.kill(BotError::Exited)),
+            Err(RecvTimeoutError::Timeout) => return Err(self.kill(BotError::Timeout(timeout))),
+        };
+        serde_json::from_str(&line).map_err(|_| self.kill(BotError::BadReply(line)))
+    }
+
+    /// Give up on the bot after `error`, returning it.
+    fn kill(&mut self, error: BotError) -> BotError {
+        self.dead = true;
+        self.writer = None;
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        error
+    }
+}
+
+impl Drop for SubprocessAgent {
+    /// Say goodbye and close the bot's stdin, giving it its `shutdown_grace` to exit before killing it.
+    fn drop(&mut self) {
+        if self.dead {
+            return
+        }
+        let grace: Duration = self.config.shutdown_grace;
+        if self.send(&Request::Bye, grace).is_ok() {
+            self.writer = None;
+            let deadline: Instant = Instant::now() + grace;
+            while Instant::now() < deadline {
+                if !matches!(self.child.try_wait(), Ok(None)) {
+                    break
+                }
+                thread::sleep(Duration::from_millis(10));
+            }
+        }
+        self.kill(BotError::Dead);
+    }
+}
+
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+    use crate::game::EuchreGame;
+    use crate::rules::RuleSet;
+
+    /// The reference bot: it answers the handshake, then always takes the first legal action.
+    const ECHO_BOT: &str = r#"
+        read hello
+        echo '{"type":"ready","name":"echo"}'
+        while read line; do
+            case "$line" in
+                *'"type":"state"'*)
+                    action=$(echo "$line" | sed 's/.*"legal_actions":\["\([A-Za-z0-9]*\)".*/\1/')
+                    echo "{\"type\":\"action\",\"action\":\"$action\"}" ;;
+            esac
+        done
+    "#;
+
+    fn bot(script: &str, config: BotConfig) -> Result<SubprocessAgent, BotError> {
+        let mut command: Command = Command::new("sh");
+        command.arg("-c").arg(script);
+        SubprocessAgent::spawn(command, config)
+    }
+
+    fn first_state() -> ScopedGameState {
+        EuchreGame::new(Some(3), Some(4), RuleSet::default()).get_state()
+    }
+
+    fn quick() -> BotConfig {
+        BotConfig { handshake_timeout: Duration::from_secs(5), move_timeout: Duration::from_millis(300), shutdown_grace: Duration::from_secs(5) }
+    }
+
+    #[test]
+    fn echo_bot_plays_a_hand() {
+        let echo: SubprocessAgent = bot(ECHO_BOT, BotConfig::default()).unwrap();
+        assert_eq!(echo.name(), Some("echo"));
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(echo),
+                                                Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(3), Some(4), false));
+        env.run();
+        assert!(env.game.is_over());
+
+        // The bot always takes the first legal action, which in the first round is Pick
+        let first: (u8, Action) = *env.get_action_history().iter().find(|(id, _)| *id == 0).unwrap();
+        assert_eq!(first, (0, Action::Pick));
+    }
+
+    #[test]
+    fn failed_handshakes() {
+        assert!(matches!(bot("read hello; echo nonsense", quick()), Err(BotError::BadReply(_))));
+        assert_eq!(bot("exit 0", quick()).err(), Some(BotError::Exited));
+        let slow: BotConfig = BotConfig { handshake_timeout: Duration::from_millis(200), ..quick() };
+        assert_eq!(bot("sleep 5", slow).err(), Some(BotError::Timeout(Duration::from_millis(200))));
+        let missing: Result<SubprocessAgent, BotError> = SubprocessAgent::spawn(Command::new("/no/such/bot"), quick());
+        assert!(matches!(missing, Err(BotError::Spawn(_))));
+    }
+
+    #[test]
+    fn illegal_replies_cost_one_move() {
+        let script: &str = r#"
+            read hello; echo '{"type":"ready"}'
+            read state; echo '{"type":"action","action":"CallH"}'
+            read state; echo '{"type":"action","action":"Pass"}'
+            read bye
+        "#;
+        let mut agent: SubprocessAgent = bot(script, quick()).unwrap();
+        let state: ScopedGameState = first_state();
+        assert_eq!(agent.name(), None);
+        assert_eq!(agent.try_decide(&state), Err(BotError::Illegal { action: Action::CallH, legal_actions: state.legal_actions.clone() }));
+        assert_eq!(agent.try_decide(&state), Ok(Action::Pass));
+        assert!(!agent.is_dead());
+    }
+
+    #[test]
+    fn dead_bots_use_the_fallback() {
+        let state: ScopedGameState = first_state();
+
+        let mut crashing: SubprocessAgent = bot(r#"read hello; echo '{"type":"ready"}'; read state; exit 1"#, quick()).unwrap();
+        assert_eq!(crashing.try_decide(&state), Err(BotError::Exited));
+        assert!(crashing.is_dead());
+        assert_eq!(crashing.try_decide(&state), Err(BotError::Dead));
+
+        let mut sleepy: SubprocessAgent = bot(r#"read hello; echo '{"type":"ready"}'; sleep 5"#, quick()).unwrap()
+            .with_fallback(Box::new(RandomAgent::new(Some(0))));
+        let action: Action = sleepy.decide_action(&state);
+        assert!(state.legal_actions.contains(&action));
+        assert!(sleepy.is_dead());
+    }
+
+    #[test]
+    fn bots_that_stop_reading_time_out() {
+        // A state too big to fit in the pipe can't be written to a bot that never reads it
+        let mut agent: SubprocessAgent = bot(r#"read hello; echo '{"type":"ready"}'; sleep 5"#, quick()).unwrap();
+        let mut state: ScopedGameState = first_state();
+        state.legal_actions = vec![Action::Pass; 100_000];
+        let start: Instant = Instant::now();
+        assert_eq!(agent.try_decide(&state), Err(BotError::Timeout(Duration::from_millis(300))));
+        assert!(agent.is_dead() && start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn bots_get_to_exit_cleanly() {
+        let marker: std::path::PathBuf = std::env::temp_dir().join(format!("euchre_rs_bye_{}", std::process::id()));
+        let script: String = format!(r#"read hello; echo '{{"type":"ready"}}'; read bye; sleep 0.2; touch '{}'"#, marker.display());
+        drop(bot(&script, quick()).unwrap());
+        assert!(marker.exists());
+        std::fs::remove_file(&marker).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "The bot sent an unexpected reply")]
+    fn panics_without_fallback() {
+        let mut agent: SubprocessAgent = bot(r#"read hello; echo '{"type":"ready"}'; read state; echo '{"type":"nope"}'"#, quick()).unwrap();
+        agent.decide_action(&first_state());
+    }
+}
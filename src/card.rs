@@ -1,8 +1,11 @@
 use std::fmt;
+use std::str::FromStr;
 
-use strum_macros::{EnumIter, Display};
+use strum_macros::{EnumIter, Display, EnumString};
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, EnumIter, Display)]
+use crate::error::EuchreError;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, EnumIter, Display, EnumString)]
 pub enum Rank {
     #[strum(to_string="9")]
     Nine = 9,
@@ -21,7 +24,7 @@ pub enum Rank {
     Unset = 0,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, EnumIter, Display)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, EnumIter, Display, EnumString)]
 pub enum Suit {
     #[strum(to_string="D")]
     Diamonds,
@@ -45,31 +48,37 @@ pub struct Card {
 impl Card {
     /// Creates new `Card` with specified suit and rank
     pub fn new(suit: Suit, rank: Rank) -> Card {
-        Card {suit: suit, rank: rank}
+        Card {suit, rank}
     }
 
     /// Returns a clone of this cards `Suit`
     pub fn suit(&self) -> Suit {
-        self.suit.clone()
+        self.suit
     }
 
     /// Returns a clone of this cards `Rank`
     pub fn rank(&self) -> Rank {
-        self.rank.clone()
+        self.rank
     }
 
     /// Returns whether this card is the left bower
-    /// given a trump suit
+    /// given a trump suit. There is no left bower if trump is unset.
     pub fn is_left(&self, trump: Suit) -> bool {
         self == &Card::new(match trump {
             Suit::Clubs => Suit::Spades,
             Suit::Diamonds => Suit::Hearts,
             Suit::Spades => Suit::Clubs,
             Suit::Hearts => Suit::Diamonds,
-            Suit::Unset => panic!("Unset Suit should be impossible here."),
+            Suit::Unset => return false,
         }, Rank::Jack)
     }
 
+    /// Returns the suit this card counts as given a trump suit,
+    /// which is only different from its printed suit for the left bower.
+    pub fn effective_suit(&self, trump: Suit) -> Suit {
+        if self.is_left(trump) {trump} else {self.suit}
+    }
+
     /// Returns whether this card is the right bower
     /// given a trump suit
     pub fn is_right(&self, trump: Suit) -> bool {
@@ -79,21 +88,15 @@ impl Card {
     /// Determines if `self` is lower than other, given trump.  
     /// Assumes that `self` is the led_suit (remember the left's suit is trump).
     pub fn is_lower(&self, trump: Suit, other: Card) -> bool {
-        let eff_suit: Suit = if self.is_left(trump) {trump} else {self.suit()};
-        let o_eff_suit: Suit = if other.is_left(trump) {trump} else {other.suit()};
+        let eff_suit: Suit = self.effective_suit(trump);
+        let o_eff_suit: Suit = other.effective_suit(trump);
 
         if eff_suit == o_eff_suit {
             // If here, and both bowers are being compared, there will be an equality on rank
             // so, looking at self.is_right will break that tie
-            if self.rank() > other.rank() || self.is_right(trump) {
-                false
-            } else {
-                true
-            }
-        } else if o_eff_suit == trump {
-            true
+            !(self.rank() > other.rank() || self.is_right(trump))
         } else {
-            false
+            o_eff_suit == trump
         }
     }
 }
@@ -104,6 +107,21 @@ impl fmt::Display for Card {
     }
 }
 
+impl FromStr for Card {
+    type Err = EuchreError;
+
+    /// Parse a card written the way it is displayed, suit then rank, e.g. `HJ` or `C9`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || EuchreError::Parse(format!("{:?} is not a card", s));
+        if !s.is_ascii() || s.len() != 2 {
+            return Err(err());
+        }
+        let suit: Suit = Suit::from_str(&s[0..1]).map_err(|_| err())?;
+        let rank: Rank = Rank::from_str(&s[1..2]).map_err(|_| err())?;
+        Ok(Card::new(suit, rank))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,6 +140,7 @@ mod tests {
         assert!(!Card::new(Suit::Clubs, Rank::Jack).is_left(Suit::Clubs));
         assert!(!Card::new(Suit::Clubs, Rank::Nine).is_left(Suit::Spades));
         assert!(!Card::new(Suit::Clubs, Rank::Jack).is_left(Suit::Diamonds));
+        assert!(!Card::new(Suit::Unset, Rank::Jack).is_left(Suit::Unset));
     }
 
     #[test]
@@ -132,6 +151,13 @@ mod tests {
         assert!(!Card::new(Suit::Diamonds, Rank::Jack).is_right(Suit::Spades));
     }
 
+    #[test]
+    fn effective_suit() {
+        assert_eq!(Card::new(Suit::Clubs, Rank::Jack).effective_suit(Suit::Spades), Suit::Spades);
+        assert_eq!(Card::new(Suit::Clubs, Rank::Jack).effective_suit(Suit::Hearts), Suit::Clubs);
+        assert_eq!(Card::new(Suit::Clubs, Rank::Ace).effective_suit(Suit::Spades), Suit::Clubs);
+    }
+
     #[test]
     fn is_lower_for_bowers() {
         let trump: Suit = Suit::Spades;
@@ -143,6 +169,16 @@ mod tests {
         assert!(!o_right.is_lower(trump, o_left));
     }
 
+    #[test]
+    fn string_to_card() {
+        assert_eq!(Card::from_str("HJ").unwrap(), Card::new(Suit::Hearts, Rank::Jack));
+        assert_eq!(Card::from_str("C9").unwrap(), Card::new(Suit::Clubs, Rank::Nine));
+        assert_eq!(Card::from_str("??").unwrap(), Card::new(Suit::Unset, Rank::Unset));
+        assert!(Card::from_str("HJ ").is_err());
+        assert!(Card::from_str("JH").is_err());
+        assert!(Card::from_str("").is_err());
+    }
+
     #[test]
     fn card_to_string() {
         assert_eq!("??", format!("{}", Card::new(Suit::Unset, Rank::Unset)));
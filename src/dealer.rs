@@ -3,18 +3,145 @@ use strum::IntoEnumIterator;
 
 use crate::card::{Card, Suit, Rank};
 use crate::player::Player;
+use crate::error::EuchreError;
 use rand::seq::SliceRandom;
 
+/// Where a card pinned by a `DealSpec` must end up.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DealSlot {
+    /// In the hand of the player with this id
+    Hand(u8),
+    /// Face up on top of the kitty
+    Flipped,
+    /// Face down in the kitty
+    Buried,
+}
+
+/// Every card of a single hand: the four players' hands, the flipped card and the three buried cards.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deal {
+    /// Indexed by player id, each holding 5 cards
+    pub hands: Vec<Vec<Card>>,
+    pub flipped_card: Card,
+    pub buried: Vec<Card>,
+}
+
+impl Deal {
+    /// Check that the deal holds each of the 24 cards exactly once,
+    /// with 5 cards in each hand and 3 buried cards.
+    pub fn validate(&self) -> Result<(), EuchreError> {
+        if self.hands.len() != 4 || self.hands.iter().any(|x| x.len() != 5) || self.buried.len() != 3 {
+            return Err(EuchreError::InvalidDeal(String::from("A deal needs 4 hands of 5 cards and 3 buried cards")));
+        }
+        let mut spec: DealSpec = DealSpec::new().flipped(self.flipped_card).buried(&self.buried);
+        for (id, hand) in self.hands.iter().enumerate() {
+            spec = spec.hand(id as u8, hand);
+        }
+        spec.validate()
+    }
+}
+
+/// Builder describing a deal where some cards are fixed in advance
+/// and the rest are dealt randomly.
+///
+/// ### Example
+///
+/// ```
+/// use euchre_rs::card::{Card, Suit, Rank};
+/// use euchre_rs::dealer::DealSpec;
+///
+/// let spec = DealSpec::new()
+///     .hand(0, &[Card::new(Suit::Hearts, Rank::Ace), Card::new(Suit::Hearts, Rank::King)])
+///     .flipped(Card::new(Suit::Hearts, Rank::Jack))
+///     .buried(&[Card::new(Suit::Clubs, Rank::Nine)]);
+/// assert!(spec.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DealSpec {
+    pins: Vec<(DealSlot, Card)>,
+}
+
+impl DealSpec {
+    /// Creates a spec with no fixed cards, which deals completely randomly.
+    pub fn new() -> DealSpec {
+        DealSpec { pins: vec![] }
+    }
+
+    /// Fix `cards` in the hand of `player_id`.
+    pub fn hand(self, player_id: u8, cards: &[Card]) -> DealSpec {
+        cards.iter().fold(self, |spec, card| spec.pin(DealSlot::Hand(player_id), *card))
+    }
+
+    /// Fix the card that is flipped face up.
+    pub fn flipped(self, card: Card) -> DealSpec {
+        self.pin(DealSlot::Flipped, card)
+    }
+
+    /// Fix `cards` among the three cards buried in the kitty.
+    pub fn buried(self, cards: &[Card]) -> DealSpec {
+        cards.iter().fold(self, |spec, card| spec.pin(DealSlot::Buried, *card))
+    }
+
+    /// Fix a single card in `slot`.
+    pub fn pin(mut self, slot: DealSlot, card: Card) -> DealSpec {
+        self.pins.push((slot, card));
+        self
+    }
+
+    /// Returns every card that has been fixed, along with where it must go.
+    pub fn pins(&self) -> &[(DealSlot, Card)] {
+        &self.pins
+    }
+
+    /// Check that the spec can be dealt.
+    /// Errors if a card is unset or pinned twice, a player id is invalid,
+    /// or a slot has more cards pinned than it can hold.
+    pub fn validate(&self) -> Result<(), EuchreError> {
+        for (i, (slot, card)) in self.pins.iter().enumerate() {
+            if card.suit() == Suit::Unset || card.rank() == Rank::Unset {
+                return Err(EuchreError::UnsetCard(*card));
+            }
+            if let DealSlot::Hand(id) = slot {
+                if *id > 3 {
+                    return Err(EuchreError::InvalidDeal(format!("Player id {} must be between 0 and 3 inclusive", id)));
+                }
+            }
+            if let Some((other, _)) = self.pins[..i].iter().find(|(_, x)| x == card) {
+                return Err(EuchreError::InvalidDeal(format!("Card {} is pinned to both {:?} and {:?}", card, other, slot)));
+            }
+        }
+
+        let mut slots: Vec<(DealSlot, usize)> = (0..4u8).map(|id| (DealSlot::Hand(id), 5)).collect();
+        slots.push((DealSlot::Flipped, 1));
+        slots.push((DealSlot::Buried, 3));
+        for (slot, size) in slots {
+            let count: usize = self.pins.iter().filter(|(x, _)| *x == slot).count();
+            if count > size {
+                return Err(EuchreError::InvalidDeal(format!("{:?} has {} cards pinned but only holds {}", slot, count, size)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the cards pinned to `slot`, in the order they were pinned.
+    fn pinned_to(&self, slot: DealSlot) -> Vec<Card> {
+        self.pins.iter().filter(|(x, _)| *x == slot).map(|(_, card)| *card).collect()
+    }
+}
+
 pub struct Dealer<'a> {
     euchre_deck: Vec<Card>,
     gen: &'a mut ChaCha8Rng,
 }
 
 impl<'a> Dealer<'a> {
-    pub fn new (gen: &'a mut ChaCha8Rng) -> Dealer {
+    pub fn new (gen: &'a mut ChaCha8Rng) -> Dealer<'a> {
         Dealer {
             euchre_deck: Self::init_euchre_deck(),
-            gen: gen,
+            gen,
         }
     }
 
@@ -31,6 +158,31 @@ impl<'a> Dealer<'a> {
         self.euchre_deck.pop().unwrap()
     }
 
+    /// Shuffle the deck and deal a full hand that honors `spec`.  
+    /// Cards not fixed by `spec` are dealt in the same order as
+    /// `deal_cards` and `flip_top_card` would deal them.
+    pub fn deal_with_spec(&mut self, spec: &DealSpec) -> Result<Deal, EuchreError> {
+        spec.validate()?;
+        self.shuffle();
+        self.euchre_deck.retain(|card| !spec.pins().iter().any(|(_, x)| x == card));
+
+        let mut hands: Vec<Vec<Card>> = vec![];
+        for id in 0..4u8 {
+            let mut hand: Vec<Card> = spec.pinned_to(DealSlot::Hand(id));
+            let at: usize = self.euchre_deck.len() - (5 - hand.len());
+            hand.append(&mut self.euchre_deck.split_off(at));
+            hands.push(hand);
+        }
+        let flipped_card: Card = match spec.pinned_to(DealSlot::Flipped).first() {
+            Some(card) => *card,
+            None => self.flip_top_card(),
+        };
+        let mut buried: Vec<Card> = spec.pinned_to(DealSlot::Buried);
+        buried.append(&mut self.euchre_deck);
+
+        Ok(Deal { hands, flipped_card, buried })
+    }
+
     fn init_euchre_deck() -> Vec<Card> {
         let mut result:Vec<Card> = vec![];
         for d_suit in Suit::iter() {
@@ -63,6 +215,91 @@ mod tests {
         assert_eq!(dealer.flip_top_card(), Card::new(Suit::Spades, Rank::Ace));
     }
 
+    #[test]
+    fn empty_spec_matches_plain_deal() {
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(5);
+        let mut dealer: Dealer = Dealer::new(&mut gen);
+        dealer.shuffle();
+        let mut players: Vec<Player> = (0..4).map(Player::new).collect();
+        for player in &mut players {
+            dealer.deal_cards(player);
+        }
+        let flipped: Card = dealer.flip_top_card();
+
+        let mut spec_gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(5);
+        let deal: Deal = Dealer::new(&mut spec_gen).deal_with_spec(&DealSpec::new()).unwrap();
+        for (i, player) in players.iter().enumerate() {
+            assert_eq!(player.hand_clone(), deal.hands[i]);
+        }
+        assert_eq!(flipped, deal.flipped_card);
+        assert_eq!(deal.buried.len(), 3);
+    }
+
+    #[test]
+    fn spec_pins_are_dealt() {
+        let hearts: Vec<Card> = vec![Card::new(Suit::Hearts, Rank::Ace),
+                                     Card::new(Suit::Hearts, Rank::King),
+                                     Card::new(Suit::Hearts, Rank::Queen),
+                                     Card::new(Suit::Hearts, Rank::Ten),
+                                     Card::new(Suit::Hearts, Rank::Nine)];
+        let spec: DealSpec = DealSpec::new()
+            .hand(0, &hearts)
+            .hand(1, &[Card::new(Suit::Diamonds, Rank::King)])
+            .flipped(Card::new(Suit::Hearts, Rank::Jack))
+            .buried(&[Card::new(Suit::Clubs, Rank::Nine)]);
+
+        for seed in 0..20 {
+            let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(seed);
+            let deal: Deal = Dealer::new(&mut gen).deal_with_spec(&spec).unwrap();
+            assert_eq!(deal.hands[0], hearts);
+            assert!(deal.hands[1].contains(&Card::new(Suit::Diamonds, Rank::King)));
+            assert_eq!(deal.flipped_card, Card::new(Suit::Hearts, Rank::Jack));
+            assert!(deal.buried.contains(&Card::new(Suit::Clubs, Rank::Nine)));
+
+            let mut all: Vec<Card> = deal.hands.concat();
+            all.push(deal.flipped_card);
+            all.extend(deal.buried.iter());
+            assert_eq!(all.len(), 24);
+            for card in Dealer::init_euchre_deck() {
+                assert!(all.contains(&card));
+            }
+        }
+    }
+
+    #[test]
+    fn spec_deal_is_seeded() {
+        let spec: DealSpec = DealSpec::new().hand(2, &[Card::new(Suit::Spades, Rank::Jack)]);
+        let mut gen_a: ChaCha8Rng = ChaCha8Rng::seed_from_u64(9);
+        let mut gen_b: ChaCha8Rng = ChaCha8Rng::seed_from_u64(9);
+        assert_eq!(Dealer::new(&mut gen_a).deal_with_spec(&spec).unwrap(),
+                   Dealer::new(&mut gen_b).deal_with_spec(&spec).unwrap());
+    }
+
+    #[test]
+    fn contradictory_specs_are_rejected() {
+        let ace: Card = Card::new(Suit::Spades, Rank::Ace);
+        let nines: Vec<Card> = vec![Card::new(Suit::Hearts, Rank::Nine),
+                                    Card::new(Suit::Diamonds, Rank::Nine),
+                                    Card::new(Suit::Spades, Rank::Nine),
+                                    Card::new(Suit::Clubs, Rank::Nine)];
+
+        assert!(DealSpec::new().hand(0, &[ace]).hand(1, &[ace]).validate().is_err());
+        assert!(DealSpec::new().hand(0, &[ace]).flipped(ace).validate().is_err());
+        assert!(DealSpec::new().hand(4, &[ace]).validate().is_err());
+        assert!(DealSpec::new().buried(&nines).validate().is_err());
+        assert!(DealSpec::new().flipped(ace).flipped(nines[0]).validate().is_err());
+        assert!(DealSpec::new().hand(3, &[Card::new(Suit::Unset, Rank::Ace)]).validate().is_err());
+
+        let mut six: Vec<Card> = nines.clone();
+        six.push(ace);
+        six.push(Card::new(Suit::Hearts, Rank::Ace));
+        assert!(DealSpec::new().hand(0, &six).validate().is_err());
+
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(1);
+        let err: EuchreError = Dealer::new(&mut gen).deal_with_spec(&DealSpec::new().hand(0, &[ace]).buried(&[ace])).unwrap_err();
+        assert_eq!(err.to_string(), "Card SA is pinned to both Hand(0) and Buried");
+    }
+
     #[test]
     fn shuffle_is_rdm() {
         let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(1);
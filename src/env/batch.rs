@@ -0,0 +1,224 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+
+use crate::agent::Agent;
+use crate::env::EuchreEnv;
+use crate::env::config::GameConfig;
+use crate::rules::RuleSet;
+use crate::utils::derive_seed;
+
+/// How many games a batch plays, and on how many threads.
+/// Change the defaults with struct update syntax:
+///
+/// ```
+/// use euchre_rs::env::batch::BatchConfig;
+///
+/// let config = BatchConfig { games: 10_000, seed: 7, ..BatchConfig::default() };
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchConfig {
+    pub games: u64,
+    /// Every game's deal and agent seeds are derived from this, see `game_seed`
+    pub seed: u64,
+    /// Worker threads, each with its own agents. Defaults to the available parallelism
+    pub threads: usize,
+    pub rules: RuleSet,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        BatchConfig {
+            games: 1000,
+            seed: 0,
+            threads: thread::available_parallelism().map_or(1, |x| x.get()),
+            rules: RuleSet::default(),
+        }
+    }
+}
+
+/// The totals of every game played in a batch.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BatchResult {
+    pub games: u64,
+    /// Points earned by team 0 (players 0 & 2) and team 1 (players 1 & 3)
+    pub points: [u64; 2],
+    /// Games in which each team scored
+    pub hands_won: [u64; 2],
+    /// Games in which nobody scored, because every player passed
+    pub misdeals: u64,
+}
+
+impl BatchResult {
+    /// Add a game whose players earned `rewards`.
+    pub fn record(&mut self, rewards: &[u8]) {
+        self.games += 1;
+        for (team, points) in rewards.iter().take(2).enumerate() {
+            self.points[team] += u64::from(*points);
+            if *points > 0 {
+                self.hands_won[team] += 1;
+            }
+        }
+        if rewards.iter().all(|x| *x == 0) {
+            self.misdeals += 1;
+        }
+    }
+
+    /// Add every game of `other`.
+    pub fn merge(&mut self, other: &BatchResult) {
+        self.games += other.games;
+        for team in 0..2 {
+            self.points[team] += other.points[team];
+            self.hands_won[team] += other.hands_won[team];
+        }
+        self.misdeals += other.misdeals;
+    }
+
+    /// Points per game earned by each team, 0 if no games were played.
+    pub fn average_points(&self) -> [f64; 2] {
+        let games: f64 = self.games.max(1) as f64;
+        [self.points[0] as f64 / games, self.points[1] as f64 / games]
+    }
+}
+
+/// The seed of game `index` of a batch seeded with `master`, the same as hand `index`
+/// of an `EuchreEnv` seeded with `master`, see `EuchreEnv::seed_for_hand`.
+/// The game is dealt with it, and the agent in each seat is reseeded with `seat_seed`.
+pub fn game_seed(master: u64, index: u64) -> u64 {
+    EuchreEnv::seed_for_hand(master, index)
+}
+
+/// The seed the agent in `seat` is reseeded with before the game dealt from `game_seed`.
+pub fn seat_seed(game_seed: u64, seat: u8) -> u64 {
+    derive_seed(game_seed, u64::from(seat))
+}
+
+/// Play `config.games` games spread across `config.threads` threads, and add up their results.
+///
+/// Each thread builds its own agents by calling `factory` with every seat, then plays the games
+/// it takes one after another, calling `Agent::reseed` before each one.
+/// Game `i` is always played from `game_seed(config.seed, i)`, so as long as the agents draw
+/// all of their randomness from their seeds, the result doesn't depend on the number of threads.
+/// Agents that learn as they play, like `QLearningAgent`, will still differ between threads.
+///
+/// ### Example
+///
+/// ```
+/// use euchre_rs::agent::{Agent, random::RandomAgent, heuristic::HeuristicAgent};
+/// use euchre_rs::env::batch::{simulate, BatchConfig};
+///
+/// let config = BatchConfig { games: 200, ..BatchConfig::default() };
+/// let result = simulate(&config, |seat| -> Box<dyn Agent> {
+///     if seat % 2 == 0 {Box::new(HeuristicAgent::default())} else {Box::new(RandomAgent::new(None))}
+/// });
+/// assert_eq!(result.games, 200);
+/// ```
+pub fn simulate<F>(config: &BatchConfig, factory: F) -> BatchResult
+    where F: Fn(u8) -> Box<dyn Agent> + Sync {
+    let next: AtomicU64 = AtomicU64::new(0);
+    let total: Mutex<BatchResult> = Mutex::new(BatchResult::default());
+    let threads: u64 = (config.threads.max(1) as u64).min(config.games.max(1));
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let agents: Vec<Box<dyn Agent>> = (0..4).map(&factory).collect();
+                let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(agents, None, Some(config.seed), false).without_log());
+                env.config.rules = config.rules;
+                let mut result: BatchResult = BatchResult::default();
+
+                loop {
+                    let index: u64 = next.fetch_add(1, Ordering::Relaxed);
+                    if index >= config.games {
+                        break
+                    }
+                    let seed: u64 = game_seed(config.seed, index);
+                    for (seat, agent) in env.config.agents.iter_mut().enumerate() {
+                        agent.reseed(seat_seed(seed, seat as u8));
+                    }
+                    env.skip_to_hand(index);
+                    result.record(&env.run());
+                }
+                total.lock().unwrap().merge(&result);
+            });
+        }
+    });
+    total.into_inner().unwrap()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::heuristic::HeuristicAgent;
+    use crate::agent::random::RandomAgent;
+    use crate::agent::scripted::ScriptedAgent;
+
+    fn random(_seat: u8) -> Box<dyn Agent> {
+        Box::new(RandomAgent::new(None))
+    }
+
+    fn batch(games: u64, seed: u64, threads: usize) -> BatchResult {
+        simulate(&BatchConfig { games, seed, threads, ..BatchConfig::default() }, random)
+    }
+
+    #[test]
+    fn same_result_on_any_number_of_threads() {
+        let serial: BatchResult = batch(300, 11, 1);
+        assert_eq!(serial.games, 300);
+        assert_eq!(batch(300, 11, 3), serial);
+        assert_eq!(batch(300, 11, 16), serial);
+        assert_ne!(batch(300, 12, 3), serial);
+    }
+
+    #[test]
+    fn fallback_agents_are_reseeded() {
+        let config = |threads: usize| BatchConfig { games: 200, seed: 5, threads, ..BatchConfig::default() };
+        let scripted = |_seat: u8| -> Box<dyn Agent> {
+            Box::new(ScriptedAgent::new(vec![]).with_fallback(Box::new(RandomAgent::new(None))))
+        };
+        assert_eq!(simulate(&config(1), scripted), simulate(&config(1), random));
+        assert_eq!(simulate(&config(4), scripted), simulate(&config(1), random));
+    }
+
+    #[test]
+    fn games_match_a_single_env() {
+        // Game 5 of a batch is hand 5 of an env with the same seeds
+        let seed: u64 = game_seed(3, 5);
+        let agents: Vec<Box<dyn Agent>> = (0..4).map(|x| Box::new(RandomAgent::new(Some(seat_seed(seed, x)))) as Box<dyn Agent>).collect();
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(agents, None, Some(3), false));
+        env.skip_to_hand(5);
+        let rewards: Vec<u8> = env.run();
+        assert_eq!(env.get_hand_seed(), Some(seed));
+
+        let mut expected: BatchResult = batch(5, 3, 2);
+        expected.record(&rewards);
+        assert_eq!(batch(6, 3, 2), expected);
+    }
+
+    #[test]
+    fn results_add_up() {
+        let mut result: BatchResult = BatchResult::default();
+        result.record(&[2, 0, 2, 0]);
+        result.record(&[0, 4, 0, 4]);
+        result.record(&[0, 0, 0, 0]);
+        assert_eq!(result, BatchResult { games: 3, points: [2, 4], hands_won: [1, 1], misdeals: 1 });
+        assert_eq!(result.average_points(), [2.0 / 3.0, 4.0 / 3.0]);
+
+        let mut twice: BatchResult = result;
+        twice.merge(&result);
+        assert_eq!(twice, BatchResult { games: 6, points: [4, 8], hands_won: [2, 2], misdeals: 2 });
+        assert_eq!(batch(0, 0, 4), BatchResult::default());
+    }
+
+    #[test]
+    fn heuristic_players_beat_random_players() {
+        let config: BatchConfig = BatchConfig { games: 400, seed: 1, threads: 4, ..BatchConfig::default() };
+        let result: BatchResult = simulate(&config, |seat| -> Box<dyn Agent> {
+            if seat % 2 == 0 {Box::new(HeuristicAgent::default())} else {Box::new(RandomAgent::new(None))}
+        });
+        assert!(result.points[0] > result.points[1], "{:?}", result);
+    }
+}
@@ -1,32 +1,184 @@
+use std::io;
+use std::path::Path;
+
 use crate::agent::Agent;
+use crate::env::logger::{JsonLinesLog, LogSink, DEFAULT_LOG_PATH};
+use crate::rules::RuleSet;
+use crate::error::EuchreError;
+
 
+/// The plain settings of a `GameConfig`, everything but its agents and log.  
+/// Unlike the config itself these can be saved and loaded with the `serde` feature.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSettings {
+    pub dealer_id: Option<u8>,
+    pub seed: Option<u64>,
+    pub verbose: bool,
+    pub rules: RuleSet,
+}
 
+
+#[allow(clippy::manual_non_exhaustive)]
 pub struct GameConfig {
     pub agents: Vec<Box<dyn Agent>>,
     pub dealer_id: Option<u8>,
     pub seed: Option<u64>,
     pub verbose: bool,
+    /// House rules for every hand, defaults to `RuleSet::default()`
+    pub rules: RuleSet,
+    /// Receives every event of the hands played, defaults to a `JsonLinesLog` writing to `output.txt`.
+    /// Use `with_log_file` or `with_log_sink` to log elsewhere, and `without_log` to turn logging off.
+    pub log_sink: Option<Box<dyn LogSink>>,
     _private: (), // exists to prevent explicit initialization
 }
 
 
 impl GameConfig {
+    /// Creates a new `GameConfig`.  
+    /// Panics if there aren't exactly 4 agents or `dealer_id` is greater than 3,
+    /// see `try_new` for a fallible version.
     pub fn new(agents: Vec<Box<dyn Agent>>, dealer_id: Option<u8>, seed: Option<u64>, verbose: bool) -> GameConfig{
+        Self::try_new(agents, dealer_id, seed, verbose).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Creates a new `GameConfig` logging to `output.txt`, replacing it if it exists.  
+    /// If `output.txt` can't be created the config logs nothing, after printing why to stderr.  
+    /// Returns an error if there aren't exactly 4 agents or `dealer_id` is greater than 3.
+    pub fn try_new(agents: Vec<Box<dyn Agent>>, dealer_id: Option<u8>, seed: Option<u64>, verbose: bool) -> Result<GameConfig, EuchreError> {
         // Validation
         if agents.len() != 4 {
-            panic!("Can only play euchre with exactly 4 players");
+            return Err(EuchreError::WrongAgentCount(agents.len()));
         }
 
-        if dealer_id.is_some() && dealer_id.unwrap() > 3{
-            panic!("Dealer ID must be between 0 and 3 inclusive");
+        if let Some(id) = dealer_id.filter(|x| *x > 3) {
+            return Err(EuchreError::InvalidDealer(id));
         }
 
-        GameConfig {
-            agents: agents,
-            dealer_id: dealer_id,
-            seed: seed,
-            verbose: verbose,
+        Ok(GameConfig {
+            agents,
+            dealer_id,
+            seed,
+            verbose,
+            rules: RuleSet::default(),
+            log_sink: Self::default_log(),
             _private: (),
+        })
+    }
+
+    /// The dealer, seed, verbosity and rules of this config.
+    pub fn settings(&self) -> GameSettings {
+        GameSettings {
+            dealer_id: self.dealer_id,
+            seed: self.seed,
+            verbose: self.verbose,
+            rules: self.rules,
         }
     }
+
+    /// Replace the dealer, seed, verbosity and rules with `settings`, keeping the agents and log.  
+    /// Returns an error if the settings' `dealer_id` is greater than 3.
+    pub fn with_settings(mut self, settings: GameSettings) -> Result<Self, EuchreError> {
+        if let Some(id) = settings.dealer_id.filter(|x| *x > 3) {
+            return Err(EuchreError::InvalidDealer(id));
+        }
+
+        self.dealer_id = settings.dealer_id;
+        self.seed = settings.seed;
+        self.verbose = settings.verbose;
+        self.rules = settings.rules;
+        Ok(self)
+    }
+
+    /// The log every config starts with, `None` if `output.txt` can't be created.
+    fn default_log() -> Option<Box<dyn LogSink>> {
+        match JsonLinesLog::to_default_file() {
+            Ok(log) => Some(Box::new(log)),
+            Err(e) => {
+                eprintln!("Could not create the game log {}, playing without it: {}", DEFAULT_LOG_PATH, e);
+                None
+            },
+        }
+    }
+
+    /// Log every event to `output.txt` again, replacing it if it exists.
+    pub fn with_default_log(self) -> io::Result<Self> {
+        self.with_log_file(DEFAULT_LOG_PATH)
+    }
+
+    /// Log every event to the file at `path`, replacing it if it exists.
+    pub fn with_log_file<P: AsRef<Path>>(self, path: P) -> io::Result<Self> {
+        Ok(self.with_log_sink(Box::new(JsonLinesLog::to_file(path)?)))
+    }
+
+    /// Log every event to `sink`.
+    pub fn with_log_sink(mut self, sink: Box<dyn LogSink>) -> Self {
+        self.log_sink = Some(sink);
+        self
+    }
+
+    /// Don't log anything.
+    pub fn without_log(mut self) -> Self {
+        self.log_sink = None;
+        self
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::random::RandomAgent;
+
+    fn agents(count: usize) -> Vec<Box<dyn Agent>> {
+        (0..count).map(|_| Box::new(RandomAgent::new(None)) as Box<dyn Agent>).collect()
+    }
+
+    #[test]
+    fn try_new_valid() {
+        assert!(GameConfig::try_new(agents(4), Some(3), None, false).is_ok());
+        assert!(GameConfig::try_new(agents(4), None, None, false).is_ok());
+    }
+
+    #[test]
+    fn try_new_wrong_agent_count() {
+        assert_eq!(GameConfig::try_new(agents(3), None, None, false).err(), Some(EuchreError::WrongAgentCount(3)));
+    }
+
+    #[test]
+    fn try_new_invalid_dealer() {
+        assert_eq!(GameConfig::try_new(agents(4), Some(4), None, false).err(), Some(EuchreError::InvalidDealer(4)));
+    }
+
+    #[test]
+    fn log_builders() {
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("euchre_rs_config_log_{}.txt", std::process::id()));
+        let config: GameConfig = GameConfig::new(agents(4), None, None, false);
+        assert!(config.log_sink.is_some() && std::path::Path::new(DEFAULT_LOG_PATH).exists());
+        let config: GameConfig = config.without_log();
+        assert!(config.log_sink.is_none());
+        let config: GameConfig = config.with_log_file(&path).unwrap();
+        assert!(config.log_sink.is_some() && path.exists());
+        assert!(config.without_log().log_sink.is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn settings_round_trip() {
+        let settings: GameSettings = GameSettings {
+            dealer_id: Some(2),
+            seed: Some(7),
+            verbose: false,
+            rules: RuleSet { stick_the_dealer: false, ..RuleSet::default() },
+        };
+        let config: GameConfig = GameConfig::new(agents(4), None, None, true).with_settings(settings).unwrap();
+        assert_eq!(config.settings(), settings);
+        assert_eq!(config.with_settings(GameSettings { dealer_id: Some(4), ..settings }).err(), Some(EuchreError::InvalidDealer(4)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_wrong_agent_count() {
+        GameConfig::new(agents(5), None, None, false);
+    }
 }
@@ -0,0 +1,259 @@
+use crate::env::EuchreEnv;
+use crate::error::EuchreError;
+
+/// The points each team earned in a single hand of a match.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandRecord {
+    pub dealer_id: u8,
+    /// Points earned by team 0 (players 0 & 2) and team 1 (players 1 & 3)
+    pub points: [u8; 2],
+    /// Seed the hand was dealt with, `None` if the config isn't seeded, see `EuchreEnv::get_hand_seed`
+    pub seed: Option<u64>,
+}
+
+/// The outcome of a full match of euchre.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchResult {
+    /// 0 for players 0 & 2, 1 for players 1 & 3
+    pub winning_team: u8,
+    pub final_score: [u8; 2],
+    /// One record per hand, in the order they were played
+    pub history: Vec<HandRecord>,
+}
+
+/// Plays successive hands of euchre on an `EuchreEnv` until one team reaches the target score.
+pub struct EuchreMatch {
+    pub env: EuchreEnv,
+    target_score: u8,
+}
+
+impl EuchreMatch {
+    /// Create a match played to the `points_to_win` of the config's rules.  
+    /// Panics if the rules' `points_to_win` is 0, see `with_target_score` for a fallible version.
+    pub fn new(env: EuchreEnv) -> EuchreMatch {
+        let target_score: u8 = env.config.rules.points_to_win;
+        Self::with_target_score(env, target_score).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Create a match played to `target_score` points.  
+    /// Returns an error if `target_score` is 0.
+    pub fn with_target_score(env: EuchreEnv, target_score: u8) -> Result<EuchreMatch, EuchreError> {
+        if target_score == 0 {
+            return Err(EuchreError::InvalidTargetScore(target_score));
+        }
+
+        Ok(EuchreMatch {
+            env,
+            target_score,
+        })
+    }
+
+    pub fn target_score(&self) -> u8 {
+        self.target_score
+    }
+
+    /// Play hands until a team reaches the target score.
+    ///
+    /// The first hand is dealt by the dealer in the config (random if `None`),
+    /// after which the deal rotates one seat to the left each hand.
+    /// After a misdeal the same dealer deals again if the rules set `redeal_after_misdeal`.
+    pub fn run(&mut self) -> MatchResult {
+        let mut score: [u8; 2] = [0, 0];
+        let mut history: Vec<HandRecord> = vec![];
+        let mut dealer_id: Option<u8> = self.env.config.dealer_id;
+
+        while score[0] < self.target_score && score[1] < self.target_score {
+            let rewards: Vec<u8> = self.env.run_with_dealer(dealer_id);
+            let dealt_by: u8 = self.env.game.get_dealer_id();
+            let points: [u8; 2] = [rewards[0], rewards[1]];
+
+            // Saturate, so a target close to `u8::MAX` can't overflow the score
+            score[0] = score[0].saturating_add(points[0]);
+            score[1] = score[1].saturating_add(points[1]);
+            history.push(HandRecord { dealer_id: dealt_by, points, seed: self.env.get_hand_seed() });
+
+            if self.env.config.verbose {
+                println!("Score: {} - {}", score[0], score[1]);
+            }
+            let redeal: bool = self.env.game.is_misdeal() && self.env.config.rules.redeal_after_misdeal;
+            dealer_id = Some(if redeal {dealt_by} else {(dealt_by + 1) % 4});
+        }
+
+        let result: MatchResult = MatchResult {
+            winning_team: if score[0] >= self.target_score {0} else {1},
+            final_score: score,
+            history,
+        };
+        self.env.notify_match_end(&result);
+        result
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::agent::{Agent, random::RandomAgent};
+    use crate::env::config::GameConfig;
+    use crate::env::observer::GameObserver;
+    use crate::game::scoped_state::ScopedGameState;
+    use crate::rules::RuleSet;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    /// Passes for its first two decisions, then takes the first legal action.
+    struct PassTwiceAgent {
+        decisions: u8,
+    }
+
+    impl Agent for PassTwiceAgent {
+        fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+            self.decisions += 1;
+            if self.decisions <= 2 && state.legal_actions.contains(&Action::Pass) {
+                Action::Pass
+            } else {
+                state.legal_actions[0]
+            }
+        }
+    }
+
+    fn all_pass_first_hand(stick_the_dealer: bool, redeal_after_misdeal: bool) -> MatchResult {
+        let players: Vec<Box<dyn Agent>> = (0..4).map(|_| Box::new(PassTwiceAgent {decisions: 0}) as Box<dyn Agent>).collect();
+        let mut config: GameConfig = GameConfig::new(players, Some(1), Some(3), false);
+        config.rules = RuleSet {
+            stick_the_dealer,
+            redeal_after_misdeal,
+            ..RuleSet::default()
+        };
+        EuchreMatch::new(EuchreEnv::new(config)).run()
+    }
+
+    fn random_env(dealer_id: Option<u8>, seed: Option<u64>) -> EuchreEnv {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3))),
+                                                Box::new(RandomAgent::new(Some(4)))];
+        EuchreEnv::new(GameConfig::new(players, dealer_id, seed, false))
+    }
+
+    #[test]
+    fn match_reaches_target() {
+        let mut game_match: EuchreMatch = EuchreMatch::new(random_env(None, None));
+        let result: MatchResult = game_match.run();
+
+        let winner: usize = usize::from(result.winning_team);
+        assert!(result.final_score[winner] >= 10);
+        assert!(result.final_score[1 - winner] < 10);
+    }
+
+    #[test]
+    fn target_comes_from_rules() {
+        let mut env: EuchreEnv = random_env(None, None);
+        env.config.rules.points_to_win = 3;
+        let mut game_match: EuchreMatch = EuchreMatch::new(env);
+        assert_eq!(game_match.target_score(), 3);
+
+        let result: MatchResult = game_match.run();
+        assert!(result.final_score[usize::from(result.winning_team)] >= 3);
+    }
+
+    #[test]
+    fn highest_target_does_not_overflow() {
+        let result: MatchResult = EuchreMatch::with_target_score(random_env(Some(0), Some(6)), u8::MAX).unwrap().run();
+        assert_eq!(result.final_score[usize::from(result.winning_team)], u8::MAX);
+    }
+
+    #[test]
+    fn misdeal_redeals_with_same_dealer() {
+        let result: MatchResult = all_pass_first_hand(false, true);
+        assert_eq!(result.history[0].points, [0, 0]);
+        assert_eq!(result.history[0].dealer_id, 1);
+        assert_eq!(result.history[1].dealer_id, 1);
+    }
+
+    #[test]
+    fn misdeal_passes_the_deal() {
+        let result: MatchResult = all_pass_first_hand(false, false);
+        assert_eq!(result.history[0].points, [0, 0]);
+        assert_eq!(result.history[0].dealer_id, 1);
+        assert_eq!(result.history[1].dealer_id, 2);
+    }
+
+    #[test]
+    fn stuck_dealer_is_not_redealt() {
+        for redeal_after_misdeal in [false, true] {
+            let result: MatchResult = all_pass_first_hand(true, redeal_after_misdeal);
+            assert_ne!(result.history[0].points, [0, 0]);
+            assert_eq!(result.history[0].dealer_id, 1);
+            assert_eq!(result.history[1].dealer_id, 2);
+        }
+    }
+
+    #[test]
+    fn history_sums_to_final_score() {
+        let mut game_match: EuchreMatch = EuchreMatch::with_target_score(random_env(None, None), 15).unwrap();
+        let result: MatchResult = game_match.run();
+
+        let team_0: u8 = result.history.iter().map(|x| x.points[0]).sum();
+        let team_1: u8 = result.history.iter().map(|x| x.points[1]).sum();
+        assert_eq!([team_0, team_1], result.final_score);
+    }
+
+    #[test]
+    fn hands_get_their_own_seeds() {
+        let result: MatchResult = EuchreMatch::new(random_env(Some(2), Some(7))).run();
+        for (i, hand) in result.history.iter().enumerate() {
+            assert_eq!(hand.seed, Some(EuchreEnv::seed_for_hand(7, i as u64)));
+        }
+        assert!(EuchreMatch::new(random_env(None, None)).run().history.iter().all(|x| x.seed.is_none()));
+    }
+
+    #[test]
+    fn dealer_rotates_left() {
+        let mut game_match: EuchreMatch = EuchreMatch::new(random_env(Some(2), Some(7)));
+        let result: MatchResult = game_match.run();
+
+        assert_eq!(result.history[0].dealer_id, 2);
+        for (i, hand) in result.history.iter().enumerate() {
+            assert_eq!(hand.dealer_id, ((2 + i) % 4) as u8);
+        }
+    }
+
+    struct MatchCounter {
+        results: Rc<RefCell<Vec<MatchResult>>>,
+    }
+
+    impl GameObserver for MatchCounter {
+        fn on_match_end(&mut self, result: &MatchResult) {
+            self.results.borrow_mut().push(result.clone());
+        }
+    }
+
+    #[test]
+    fn observers_see_match_end() {
+        let results: Rc<RefCell<Vec<MatchResult>>> = Rc::new(RefCell::new(vec![]));
+        let mut env: EuchreEnv = random_env(None, Some(4));
+        env.add_observer(Box::new(MatchCounter { results: Rc::clone(&results) }));
+        let mut game_match: EuchreMatch = EuchreMatch::new(env);
+
+        let result: MatchResult = game_match.run();
+        assert_eq!(*results.borrow(), vec![result]);
+    }
+
+    #[test]
+    fn zero_target_is_rejected() {
+        let result = EuchreMatch::with_target_score(random_env(None, None), 0);
+        assert_eq!(result.err().map(|e| e.to_string()), Some(String::from("A match must be played to at least 1 point, got 0")));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_points_to_win_panics() {
+        let mut env: EuchreEnv = random_env(None, None);
+        env.config.rules.points_to_win = 0;
+        EuchreMatch::new(env);
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::action::Action;
+use crate::card::Card;
+use crate::game::trick::Trick;
+
+/// Where game logs are written when no other path is given.
+pub const DEFAULT_LOG_PATH: &str = "output.txt";
+
+/// Something that happened during a hand, as reported to a `LogSink`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LogEvent {
+    /// The cards were dealt, indexed by player id
+    Deal {
+        dealer_id: u8,
+        hands: Vec<Vec<Card>>,
+    },
+    /// The dealer turned up the top card of the kitty
+    Flip {
+        dealer_id: u8,
+        card: Card,
+    },
+    /// A pass, pick up or call, alone or not
+    Bid {
+        player_id: u8,
+        action: Action,
+    },
+    /// The dealer discarded after picking up
+    Discard {
+        player_id: u8,
+        action: Action,
+        card: Card,
+    },
+    /// A card was played to the center
+    Play {
+        player_id: u8,
+        action: Action,
+        card: Card,
+    },
+    /// The last card of a trick was played, and `trick.winner` took it
+    TrickWon(Trick),
+    /// The hand is over; `calling_player_id` is `None` after a misdeal
+    HandScored {
+        calling_player_id: Option<u8>,
+        alone: bool,
+        tricks: Vec<u8>,
+        points: Vec<u8>,
+    },
+}
+
+impl LogEvent {
+    /// The event for `player_id` taking `action`.
+    pub fn for_action(player_id: u8, action: Action) -> LogEvent {
+        match Action::action_to_card(action) {
+            Ok(card) if (action as u8) < 30 => LogEvent::Play { player_id, action, card },
+            Ok(card) => LogEvent::Discard { player_id, action, card },
+            Err(_) => LogEvent::Bid { player_id, action },
+        }
+    }
+
+    /// Write the event as a single line JSON object.
+    /// Every record has an `event` name; records about a single player carry their `seat`,
+    /// and cards and actions are written as they are displayed, e.g. `"HJ"` and `"HJPlay"`.
+    pub fn to_json(&self) -> String {
+        let cards = |cards: &[Card]| format!("[{}]", cards.iter().map(|x| format!("\"{}\"", x)).collect::<Vec<String>>().join(","));
+        let numbers = |nums: &[u8]| format!("[{}]", nums.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(","));
+
+        match self {
+            LogEvent::Deal { dealer_id, hands } => {
+                let hands: Vec<String> = hands.iter().map(|x| cards(x)).collect();
+                format!("{{\"event\":\"deal\",\"seat\":{},\"hands\":[{}]}}", dealer_id, hands.join(","))
+            },
+            LogEvent::Flip { dealer_id, card } => {
+                format!("{{\"event\":\"flip\",\"seat\":{},\"card\":\"{}\"}}", dealer_id, card)
+            },
+            LogEvent::Bid { player_id, action } => {
+                format!("{{\"event\":\"bid\",\"seat\":{},\"action\":\"{}\"}}", player_id, action)
+            },
+            LogEvent::Discard { player_id, action, card } => {
+                format!("{{\"event\":\"discard\",\"seat\":{},\"action\":\"{}\",\"card\":\"{}\"}}", player_id, action, card)
+            },
+            LogEvent::Play { player_id, action, card } => {
+                format!("{{\"event\":\"play\",\"seat\":{},\"action\":\"{}\",\"card\":\"{}\"}}", player_id, action, card)
+            },
+            LogEvent::TrickWon(trick) => {
+                format!("{{\"event\":\"trick_won\",\"seat\":{},\"order\":{},\"cards\":{}}}",
+                        trick.winner, numbers(&trick.order), cards(&trick.cards))
+            },
+            LogEvent::HandScored { calling_player_id, alone, tricks, points } => {
+                let caller: String = calling_player_id.map_or(String::from("null"), |x| x.to_string());
+                format!("{{\"event\":\"hand_scored\",\"seat\":{},\"alone\":{},\"tricks\":{},\"points\":{}}}",
+                        caller, alone, numbers(tricks), numbers(points))
+            },
+        }
+    }
+}
+
+/// Receives every event of the hands played by an `EuchreEnv`.
+pub trait LogSink {
+    fn log(&mut self, event: &LogEvent) -> io::Result<()>;
+}
+
+/// A `LogSink` writing one JSON record per line, see `LogEvent::to_json`.
+pub struct JsonLinesLog<W: Write> {
+    writer: W,
+}
+
+impl JsonLinesLog<BufWriter<File>> {
+    /// Log to `output.txt`, replacing it if it exists.
+    pub fn to_default_file() -> io::Result<Self> {
+        Self::to_file(DEFAULT_LOG_PATH)
+    }
+
+    /// Log to the file at `path`, replacing it if it exists.
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonLinesLog<W> {
+    /// Log to any writer.
+    pub fn new(writer: W) -> Self {
+        JsonLinesLog { writer }
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl<W: Write> LogSink for JsonLinesLog<W> {
+    fn log(&mut self, event: &LogEvent) -> io::Result<()> {
+        writeln!(self.writer, "{}", event.to_json())?;
+        if matches!(event, LogEvent::HandScored { .. }) {
+            self.writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Suit, Rank};
+
+    #[test]
+    fn action_events() {
+        assert_eq!(LogEvent::for_action(1, Action::CallH), LogEvent::Bid { player_id: 1, action: Action::CallH });
+        assert_eq!(LogEvent::for_action(0, Action::S9Discard),
+                   LogEvent::Discard { player_id: 0, action: Action::S9Discard, card: Card::new(Suit::Spades, Rank::Nine) });
+        assert_eq!(LogEvent::for_action(3, Action::HJPlay),
+                   LogEvent::Play { player_id: 3, action: Action::HJPlay, card: Card::new(Suit::Hearts, Rank::Jack) });
+    }
+
+    #[test]
+    fn json_records() {
+        let jack: Card = Card::new(Suit::Hearts, Rank::Jack);
+        let nine: Card = Card::new(Suit::Spades, Rank::Nine);
+        assert_eq!(LogEvent::Deal { dealer_id: 2, hands: vec![vec![jack, nine], vec![]] }.to_json(),
+                   r#"{"event":"deal","seat":2,"hands":[["HJ","S9"],[]]}"#);
+        assert_eq!(LogEvent::Flip { dealer_id: 2, card: jack }.to_json(), r#"{"event":"flip","seat":2,"card":"HJ"}"#);
+        assert_eq!(LogEvent::for_action(1, Action::PickAlone).to_json(), r#"{"event":"bid","seat":1,"action":"PickAlone"}"#);
+        assert_eq!(LogEvent::for_action(0, Action::S9Discard).to_json(),
+                   r#"{"event":"discard","seat":0,"action":"S9Discard","card":"S9"}"#);
+        assert_eq!(LogEvent::for_action(3, Action::HJPlay).to_json(), r#"{"event":"play","seat":3,"action":"HJPlay","card":"HJ"}"#);
+        assert_eq!(LogEvent::TrickWon(Trick { winner: 1, order: vec![0, 1], cards: vec![nine, jack] }).to_json(),
+                   r#"{"event":"trick_won","seat":1,"order":[0,1],"cards":["S9","HJ"]}"#);
+        assert_eq!(LogEvent::HandScored { calling_player_id: None, alone: false, tricks: vec![0; 4], points: vec![0; 4] }.to_json(),
+                   r#"{"event":"hand_scored","seat":null,"alone":false,"tricks":[0,0,0,0],"points":[0,0,0,0]}"#);
+    }
+
+    #[test]
+    fn one_record_per_line() {
+        let mut log: JsonLinesLog<Vec<u8>> = JsonLinesLog::new(vec![]);
+        log.log(&LogEvent::for_action(1, Action::Pass)).unwrap();
+        log.log(&LogEvent::for_action(2, Action::Pick)).unwrap();
+
+        let text: String = String::from_utf8(log.into_inner().unwrap()).unwrap();
+        assert_eq!(text, "{\"event\":\"bid\",\"seat\":1,\"action\":\"Pass\"}\n{\"event\":\"bid\",\"seat\":2,\"action\":\"Pick\"}\n");
+    }
+}
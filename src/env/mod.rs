@@ -2,54 +2,197 @@ use crate::game::EuchreGame;
 use crate::agent::Agent;
 use crate::game::scoped_state::ScopedGameState;
 use crate::action::Action;
+use crate::dealer::DealSpec;
+use crate::error::EuchreError;
+use crate::utils::derive_seed;
 
+pub mod batch;
 pub mod config;
+pub mod euchre_match;
+pub mod logger;
+pub mod observer;
+#[cfg(feature = "json")]
+pub mod server;
 use self::config::GameConfig;
+use self::euchre_match::MatchResult;
+use self::logger::LogEvent;
+use self::observer::GameObserver;
 
 pub struct EuchreEnv {
     pub game: EuchreGame,
     pub config: GameConfig,
 
     action_history: Vec<(u8, Action)>,
+    observers: Vec<Box<dyn GameObserver>>,
+    /// Index of the current hand in the config's seeded run
+    hand: u64,
+    /// Seed the current hand was dealt with
+    hand_seed: Option<u64>,
+    /// Index of the hand the next reset deals
+    next_hand: u64,
+    /// The first error the log sink returned, after which play went on without reporting more
+    log_error: Option<std::io::Error>,
+    /// Whether the agents, observers and log have been told about the current deal
+    deal_announced: bool,
 }
 
 impl EuchreEnv {
+    /// Create an environment whose game is hand 0 of the config's seeded run.  
+    /// Every reset deals the next hand of the run, see `seed_for_hand`.
     pub fn new(config: GameConfig) -> EuchreEnv {
 
+        let hand_seed: Option<u64> = config.seed.map(|x| Self::seed_for_hand(x, 0));
         let game: EuchreGame = EuchreGame::new(config.dealer_id,
-                                               config.seed,
+                                               hand_seed,
+                                               config.rules,
                                                );
         EuchreEnv {
-            game: game,
-            config: config,
+            game,
+            config,
             action_history: vec![],
+            observers: vec![],
+            hand: 0,
+            hand_seed,
+            next_hand: 0,
+            log_error: None,
+            deal_announced: false,
         }
     }
 
+    /// The seed hand `hand` of a run seeded with `seed` is dealt with.  
+    /// Each hand gets its own seed, so a seeded environment deals a different hand every reset,
+    /// and the same hands in the same order every time it is seeded the same way.
+    pub fn seed_for_hand(seed: u64, hand: u64) -> u64 {
+        derive_seed(seed, hand)
+    }
+
+    /// Create an environment whose game starts from `moves` already played on a deal honoring `spec`,
+    /// with the cards it leaves open dealt as in hand 0 of the config's seeded run.  
+    /// Use `resume` to let the configured agents finish the game from that point.
+    /// Returns an error if `spec` is contradictory or a move is illegal.
+    pub fn from_moves(config: GameConfig, spec: &DealSpec, moves: Vec<(u8, Action)>) -> Result<EuchreEnv, EuchreError> {
+        let hand_seed: Option<u64> = config.seed.map(|x| Self::seed_for_hand(x, 0));
+        let game: EuchreGame = EuchreGame::from_moves(config.dealer_id,
+                                                      hand_seed,
+                                                      config.rules,
+                                                      spec,
+                                                      &moves)?;
+        Ok(EuchreEnv {
+            game,
+            config,
+            action_history: moves,
+            observers: vec![],
+            hand: 0,
+            hand_seed,
+            next_hand: 0,
+            log_error: None,
+            deal_announced: false,
+        })
+    }
+
     /// Run a random euchre game from start to finish.
     /// 
     /// This function returns the rewards each player got at the end of the game.
     pub fn run(&mut self) -> Vec<u8> {
-        self.reset();
+        self.run_with_dealer(self.config.dealer_id)
+    }
 
+    /// Run a euchre game from start to finish with `dealer_id` dealing,
+    /// instead of the dealer stored in the config.
+    /// 
+    /// This function returns the rewards each player got at the end of the game.
+    pub fn run_with_dealer(&mut self, dealer_id: Option<u8>) -> Vec<u8> {
+        self.reset_with_dealer(dealer_id);
+        self.resume()
+    }
+
+    /// Let the agents play the current game from wherever it is to the end, without resetting it.
+    /// 
+    /// This function returns the rewards each player got at the end of the game.
+    pub fn resume(&mut self) -> Vec<u8> {
         let mut state: ScopedGameState = self.game.get_state();
         let mut curr_player = state.current_actor;
         while !self.game.is_over() {
             self.log_important_game_info(&state);
             let act: crate::action::Action = self.config.agents.get_mut(usize::from(curr_player)).unwrap().decide_action(&state);
-            self.record_action((curr_player, act));
-            (state, curr_player) = self.game.step(act);
+            let actor: u8 = curr_player;
+            (state, curr_player) = self.game.step(act)
+                .unwrap_or_else(|e| panic!("Player {} took an illegal action: {}", actor, e));
+            self.record_action((actor, act));
         }
         self.game.get_rewards().unwrap()
     }
 
-    /// Create a new EuchreGame re-using config information.  
+    /// Create a new EuchreGame re-using config information, dealing the next hand of the seeded run.  
     pub fn reset(&mut self) {
-        self.game = EuchreGame::new(self.config.dealer_id, self.config.seed);
+        self.reset_with_dealer(self.config.dealer_id);
+    }
+
+    /// Create a new EuchreGame dealt by `dealer_id`, re-using the rest of the config.
+    fn reset_with_dealer(&mut self, dealer_id: Option<u8>) {
+        self.hand = self.next_hand;
+        self.next_hand += 1;
+        self.hand_seed = self.config.seed.map(|x| Self::seed_for_hand(x, self.hand));
+        self.game = EuchreGame::new(dealer_id, self.hand_seed, self.config.rules);
+        self.action_history.clear();
+        self.deal_announced = false;
+    }
+
+    /// Make the next reset, or run, deal hand `hand` of the seeded run,
+    /// continuing with the hands after it.
+    pub fn skip_to_hand(&mut self, hand: u64) {
+        self.next_hand = hand;
+    }
+
+    /// Returns the index of the current hand in the seeded run, counting from 0.
+    pub fn get_hand_index(&self) -> u64 {
+        self.hand
+    }
+
+    /// Returns the seed the current hand was dealt with, `None` if the config isn't seeded.
+    pub fn get_hand_seed(&self) -> Option<u64> {
+        self.hand_seed
+    }
+
+    /// Returns every action taken in the current game, along with the id of the player who took it.
+    pub fn get_action_history(&self) -> &[(u8, Action)] {
+        &self.action_history
+    }
+
+    /// Take back the last action of the current game, see `EuchreGame::undo`.
+    pub fn undo(&mut self) -> Option<(u8, Action)> {
+        let undone: Option<(u8, Action)> = self.game.undo();
+        if undone.is_some() {
+            self.action_history.pop();
+        }
+        undone
+    }
+
+    /// Rewind the current game to its first `n` actions, see `EuchreGame::undo_to`.
+    pub fn undo_to(&mut self, n: usize) -> Result<(), EuchreError> {
+        self.game.undo_to(n)?;
+        self.action_history.truncate(n);
+        Ok(())
+    }
+
+    /// Add an observer that is told about every deal, action, trick and hand played from now on.
+    pub fn add_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.push(observer);
+    }
+
+    /// Returns the observers in the order they were added.
+    pub fn get_observers(&self) -> &[Box<dyn GameObserver>] {
+        &self.observers
+    }
+
+    /// Remove and return every observer.
+    pub fn take_observers(&mut self) -> Vec<Box<dyn GameObserver>> {
+        std::mem::take(&mut self.observers)
     }
 
     /// Returns a read only reference for the agent with index of `index`.  
     /// Result will contain `None` if invalid index is passed.
+    #[allow(clippy::borrowed_box)]
     pub fn get_agent(&self, index: usize) -> Option<&Box<dyn Agent>> {
         self.config.agents.get(index)
     }
@@ -60,26 +203,112 @@ impl EuchreEnv {
         self.config.agents.get_mut(index)
     }
 
-    /// Returns the id of the player who took this action, given the game state.
+    /// Store an action that successfully advanced the game, along with the id of the player who took it.
     fn record_action(&mut self, record: (u8, Action)) {
         self.action_history.push(record);
         self.log_action_took(&record);
     }
 
-    /// Log the action that was taken after successfully advancing the game. 
-    fn log_action_took(&self, record: &(u8, Action)) {
-        // TODO: log that it happened in some type of logger.
-
+    /// Tell the agents, observers and log about the action that was taken after successfully advancing the game,
+    /// along with the trick or hand it completed, if any.
+    fn log_action_took(&mut self, record: &(u8, Action)) {
         if self.config.verbose {
             println!("Player {}: {}", record.0, record.1);
         }
+
+        let event: LogEvent = LogEvent::for_action(record.0, record.1);
+        for (seat, agent) in self.config.agents.iter_mut().enumerate() {
+            if seat == usize::from(record.0) || !matches!(event, LogEvent::Discard { .. }) {
+                agent.observe_action(record.0, record.1);
+            }
+        }
+        for observer in self.observers.iter_mut() {
+            observer.on_action(record.0, record.1, &self.game);
+        }
+        self.log_event(event);
+
+        if let Some(trick) = self.game.get_last_trick() {
+            for agent in self.config.agents.iter_mut() {
+                agent.on_trick_end(trick.winner, &trick.cards);
+            }
+            for observer in self.observers.iter_mut() {
+                observer.on_trick_complete(&trick);
+            }
+            self.log_event(LogEvent::TrickWon(trick));
+        }
+        if let Some(points) = self.game.get_rewards() {
+            for agent in self.config.agents.iter_mut() {
+                agent.on_hand_end(&points);
+            }
+            for observer in self.observers.iter_mut() {
+                observer.on_hand_end(&self.game, &points);
+            }
+            let state: ScopedGameState = self.game.get_state();
+            let mut tricks: Vec<u8> = vec![0; 4];
+            for trick in self.game.get_tricks() {
+                tricks[usize::from(trick.winner)] += 1;
+            }
+            self.log_event(LogEvent::HandScored {
+                calling_player_id: state.calling_actor,
+                alone: state.alone_actor.is_some(),
+                tricks,
+                points,
+            });
+        }
     }
 
-    /// Given the current state, log important information that make the player's actions make sense.
-    fn log_important_game_info(&self, state: &ScopedGameState) {
-        if self.action_history.len() == 0 {
-            if self.config.verbose {
-                println!("Flipped: {}", state.flipped_card)
+    /// The first time the agents play a hand, tell the agents, observers and log about the deal,
+    /// even when the hand was resumed after some moves, followed by the moves it was resumed after.
+    /// Does nothing once the deal has been announced.
+    fn log_important_game_info(&mut self, state: &ScopedGameState) {
+        if self.deal_announced {
+            return
+        }
+        self.deal_announced = true;
+        if self.config.verbose {
+            println!("Flipped: {}", state.flipped_card)
+        }
+        for (seat, agent) in self.config.agents.iter_mut().enumerate() {
+            agent.on_hand_start(seat as u8, &self.game.get_deal().hands[seat]);
+        }
+        for observer in self.observers.iter_mut() {
+            observer.on_deal(state.dealer_actor, self.game.get_deal());
+        }
+        self.log_event(LogEvent::Deal { dealer_id: state.dealer_actor, hands: self.game.get_deal().hands.clone() });
+        self.log_event(LogEvent::Flip { dealer_id: state.dealer_actor, card: state.flipped_card });
+
+        // Replay the moves a resumed game starts after, so everyone sees them as if they were just taken
+        let resumed: EuchreGame = self.game.clone();
+        self.game.undo_to(0).unwrap();
+        for (player_id, action) in resumed.get_action_history() {
+            self.game.step(action).unwrap();
+            self.log_action_took(&(player_id, action));
+        }
+        self.game = resumed;
+    }
+
+    /// Tell every observer that a match played on this environment finished.
+    pub(crate) fn notify_match_end(&mut self, result: &MatchResult) {
+        for observer in self.observers.iter_mut() {
+            observer.on_match_end(result);
+        }
+    }
+
+    /// Returns the first error the log sink returned, if any.  
+    /// A failing log never stops the game; the first error is printed to stderr and kept here.
+    pub fn get_log_error(&self) -> Option<&std::io::Error> {
+        self.log_error.as_ref()
+    }
+
+    /// Pass `event` to the config's log sink, if there is one.
+    fn log_event(&mut self, event: LogEvent) {
+        let Some(sink) = self.config.log_sink.as_mut() else {
+            return
+        };
+        if let Err(e) = sink.log(&event) {
+            if self.log_error.is_none() {
+                eprintln!("Failed to write to the game log, play continues without it: {}", e);
+                self.log_error = Some(e);
             }
         }
     }
@@ -91,6 +320,12 @@ impl EuchreEnv {
 mod tests {
     use super::*;
     use crate::agent::random::RandomAgent;
+    use crate::env::logger::{JsonLinesLog, LogSink};
+    use crate::card::Card;
+    use crate::dealer::Deal;
+    use crate::game::trick::Trick;
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
     #[test]
     fn run_game() {
@@ -104,4 +339,292 @@ mod tests {
         print!("{:?}", rewards);
         assert!(4 == rewards.len())
     }
+
+    fn seeded_env(seed: u64) -> EuchreEnv {
+        let players: Vec<Box<dyn Agent>> = (0..4).map(|_| Box::new(RandomAgent::new(Some(1))) as Box<dyn Agent>).collect();
+        EuchreEnv::new(GameConfig::new(players, Some(0), Some(seed), false))
+    }
+
+    #[test]
+    fn each_hand_gets_a_new_deal() {
+        let mut env: EuchreEnv = seeded_env(5);
+        let mut deals: Vec<Deal> = vec![];
+        for hand in 0..4 {
+            env.reset();
+            assert_eq!(env.get_hand_index(), hand);
+            assert_eq!(env.get_hand_seed(), Some(EuchreEnv::seed_for_hand(5, hand)));
+            deals.push(env.game.get_deal().clone());
+        }
+        assert!((1..4).all(|x| deals[x] != deals[x - 1]));
+
+        // The same seed deals the same hands, and any of them can be dealt directly
+        let mut again: EuchreEnv = seeded_env(5);
+        assert_eq!(again.game.get_deal(), &deals[0]);
+        again.skip_to_hand(2);
+        again.run();
+        assert_eq!((again.get_hand_index(), again.game.get_deal()), (2, &deals[2]));
+        again.reset();
+        assert_eq!(again.game.get_deal(), &deals[3]);
+        assert_eq!(seeded_env(6).get_hand_seed(), Some(EuchreEnv::seed_for_hand(6, 0)));
+    }
+
+    #[test]
+    fn resume_from_moves() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3))),
+                                                Box::new(RandomAgent::new(Some(4)))];
+        let config: GameConfig = GameConfig::new(players, Some(0), Some(8), false);
+        let moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pick)];
+        let mut env: EuchreEnv = EuchreEnv::from_moves(config, &DealSpec::new(), moves.clone()).unwrap();
+
+        assert_eq!(env.game.get_curr_player_id(), 0);
+        let rewards: Vec<u8> = env.resume();
+        assert!(4 == rewards.len());
+        assert_eq!(&env.get_action_history()[0..2], &moves[..]);
+        assert!(env.get_action_history().len() > 20);
+    }
+
+    #[test]
+    fn undo_keeps_history_in_sync() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3))),
+                                                Box::new(RandomAgent::new(Some(4)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(0), Some(8), false));
+        env.run();
+        let taken: Vec<(u8, Action)> = env.get_action_history().to_vec();
+
+        assert_eq!(env.undo(), taken.last().copied());
+        env.undo_to(3).unwrap();
+        assert_eq!(env.get_action_history(), &taken[0..3]);
+        assert_eq!(env.game.get_action_history(), taken[0..3].to_vec());
+        assert!(env.undo_to(4).is_err());
+
+        env.resume();
+        assert!(env.game.is_over());
+    }
+
+    /// A log sink whose disk is always full.
+    struct FullDisk {
+        attempts: Rc<RefCell<usize>>,
+    }
+
+    impl LogSink for FullDisk {
+        fn log(&mut self, _event: &LogEvent) -> std::io::Result<()> {
+            *self.attempts.borrow_mut() += 1;
+            Err(std::io::Error::other("disk full"))
+        }
+    }
+
+    #[test]
+    fn log_failures_do_not_stop_play() {
+        let attempts: Rc<RefCell<usize>> = Rc::new(RefCell::new(0));
+        let config: GameConfig = seeded_env(2).config.with_log_sink(Box::new(FullDisk { attempts: Rc::clone(&attempts) }));
+        let mut env: EuchreEnv = EuchreEnv::new(config);
+        env.run();
+        assert!(env.game.is_over());
+        assert_eq!(env.get_log_error().unwrap().to_string(), "disk full");
+        assert!(*attempts.borrow() > 2);
+    }
+
+    #[test]
+    fn log_to_file() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3))),
+                                                Box::new(RandomAgent::new(Some(4)))];
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!("euchre_rs_log_{}.txt", std::process::id()));
+        let mut config: GameConfig = GameConfig::new(players, Some(0), Some(8), false);
+        config.log_sink = Some(Box::new(JsonLinesLog::to_file(&path).unwrap()));
+        let mut env: EuchreEnv = EuchreEnv::new(config);
+        env.run();
+        let tricks: Vec<Trick> = env.game.get_tricks();
+
+        let text: String = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        // deal and flip, one line per action, one per trick, and the score
+        assert_eq!(lines.len(), 2 + env.get_action_history().len() + tricks.len() + 1);
+        assert!(lines[0].starts_with("{\"event\":\"deal\",\"seat\":0,"));
+        assert!(lines[1].starts_with("{\"event\":\"flip\",\"seat\":0,"));
+        let (player_id, action) = env.get_action_history()[0];
+        assert_eq!(lines[2], LogEvent::for_action(player_id, action).to_json());
+        assert_eq!(lines.iter().filter(|x| x.contains("\"trick_won\"")).count(), 5);
+        assert!(lines.last().unwrap().starts_with("{\"event\":\"hand_scored\""));
+    }
+
+    /// Writes a line per callback to a shared list.
+    struct Recorder {
+        events: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl GameObserver for Recorder {
+        fn on_deal(&mut self, dealer_id: u8, deal: &Deal) {
+            self.events.borrow_mut().push(format!("deal {} {}", dealer_id, deal.flipped_card));
+        }
+
+        fn on_action(&mut self, player_id: u8, action: Action, game: &EuchreGame) {
+            assert_eq!(game.get_action_history().last(), Some(&(player_id, action)));
+            self.events.borrow_mut().push(format!("action {} {}", player_id, action));
+        }
+
+        fn on_trick_complete(&mut self, trick: &Trick) {
+            self.events.borrow_mut().push(format!("trick {}", trick.winner));
+        }
+
+        fn on_hand_end(&mut self, game: &EuchreGame, rewards: &[u8]) {
+            assert!(game.is_over());
+            self.events.borrow_mut().push(format!("hand {:?}", rewards));
+        }
+    }
+
+    #[test]
+    fn resumed_games_announce_the_deal() {
+        let players: Vec<Box<dyn Agent>> = (0..4).map(|x| Box::new(RandomAgent::new(Some(x))) as Box<dyn Agent>).collect();
+        let moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pick)];
+        let mut env: EuchreEnv = EuchreEnv::from_moves(GameConfig::new(players, Some(0), Some(8), false), &DealSpec::new(), moves).unwrap();
+        let events: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+        env.add_observer(Box::new(Recorder { events: Rc::clone(&events) }));
+        env.resume();
+
+        assert_eq!(events.borrow().iter().filter(|x| x.starts_with("deal")).count(), 1);
+        assert_eq!(events.borrow()[0], format!("deal 0 {}", env.game.get_deal().flipped_card));
+    }
+
+    #[test]
+    fn observers_see_every_event() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(Some(1))),
+                                                Box::new(RandomAgent::new(Some(2))),
+                                                Box::new(RandomAgent::new(Some(3))),
+                                                Box::new(RandomAgent::new(Some(4)))];
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(2), Some(5), false));
+        let first: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+        let second: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+        env.add_observer(Box::new(Recorder { events: Rc::clone(&first) }));
+        env.add_observer(Box::new(Recorder { events: Rc::clone(&second) }));
+        let rewards: Vec<u8> = env.run();
+
+        let events: Vec<String> = first.borrow().clone();
+        assert_eq!(events, *second.borrow());
+        assert_eq!(events[0], format!("deal 2 {}", env.game.get_deal().flipped_card));
+        assert_eq!(events.iter().filter(|x| x.starts_with("action")).count(), env.get_action_history().len());
+        assert_eq!(events.iter().filter(|x| x.starts_with("trick")).count(), env.game.get_tricks().len());
+        assert_eq!(events.last().unwrap(), &format!("hand {:?}", rewards));
+        assert_eq!(env.take_observers().len(), 2);
+        assert!(env.get_observers().is_empty());
+    }
+
+    /// What the hooks showed a `CountingAgent`.
+    #[derive(Default)]
+    struct Seen {
+        seat: Option<u8>,
+        hand: Vec<Card>,
+        actions: usize,
+        discards: usize,
+        cards: Vec<Card>,
+        rewards: Option<Vec<u8>>,
+    }
+
+    /// Plays randomly while counting what the hooks show it.
+    struct CountingAgent {
+        inner: RandomAgent,
+        seen: Rc<RefCell<Seen>>,
+    }
+
+    impl Agent for CountingAgent {
+        fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+            self.inner.decide_action(state)
+        }
+
+        fn on_hand_start(&mut self, seat: u8, hand: &[Card]) {
+            let mut seen = self.seen.borrow_mut();
+            seen.seat = Some(seat);
+            seen.hand = hand.to_vec();
+        }
+
+        fn observe_action(&mut self, _seat: u8, action: Action) {
+            let mut seen = self.seen.borrow_mut();
+            seen.actions += 1;
+            if (30..=53).contains(&(action as u8)) {
+                seen.discards += 1;
+            }
+        }
+
+        fn on_trick_end(&mut self, _winner: u8, cards: &[Card]) {
+            self.seen.borrow_mut().cards.extend_from_slice(cards);
+        }
+
+        fn on_hand_end(&mut self, rewards: &[u8]) {
+            self.seen.borrow_mut().rewards = Some(rewards.to_vec());
+        }
+    }
+
+    fn counting_agents(seen: &[Rc<RefCell<Seen>>]) -> Vec<Box<dyn Agent>> {
+        (0..4).map(|i| Box::new(CountingAgent {
+            inner: RandomAgent::new(Some(i as u64)),
+            seen: Rc::clone(&seen[i]),
+        }) as Box<dyn Agent>).collect()
+    }
+
+    /// Check that every agent was shown the whole of the game `env` just finished.
+    fn assert_saw_every_hook(env: &EuchreEnv, seen: &[Rc<RefCell<Seen>>], rewards: &[u8]) {
+        let history: Vec<(u8, Action)> = env.get_action_history().to_vec();
+        let discard: Option<(u8, Action)> = history.iter().copied().find(|(_, x)| (30..=53).contains(&(*x as u8)));
+        let played: Vec<Card> = env.game.get_tricks().into_iter().flat_map(|x| x.cards).collect();
+        assert!(discard.is_some());
+
+        for (seat, agent) in seen.iter().enumerate() {
+            let agent = agent.borrow();
+            assert_eq!(agent.seat, Some(seat as u8));
+            assert_eq!(agent.hand, env.game.get_deal().hands[seat]);
+            let saw_discard: bool = discard.is_some_and(|(id, _)| usize::from(id) == seat);
+            assert_eq!(agent.discards, usize::from(saw_discard));
+            assert_eq!(agent.actions, history.len() - usize::from(discard.is_some() && !saw_discard));
+            assert_eq!(agent.cards, played);
+            assert_eq!(agent.rewards.as_deref(), Some(rewards));
+        }
+    }
+
+    #[test]
+    fn agents_see_every_hook() {
+        let seen: Vec<Rc<RefCell<Seen>>> = (0..4).map(|_| Rc::new(RefCell::new(Seen::default()))).collect();
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(counting_agents(&seen), Some(3), Some(12), false));
+        let rewards: Vec<u8> = env.run();
+        assert_saw_every_hook(&env, &seen, &rewards);
+    }
+
+    #[test]
+    fn resumed_games_replay_their_moves() {
+        // Resume halfway through a hand with a discard and finished tricks, then check the agents saw all of it
+        let players: Vec<Box<dyn Agent>> = (0..4).map(|x| Box::new(RandomAgent::new(Some(x))) as Box<dyn Agent>).collect();
+        let mut played: EuchreEnv = EuchreEnv::new(GameConfig::new(players, Some(3), Some(12), false));
+        played.run();
+        let history: &[(u8, Action)] = played.get_action_history();
+        let moves: Vec<(u8, Action)> = history[..history.len() / 2].to_vec();
+        assert!(moves.iter().filter(|(_, x)| (6..30).contains(&(*x as u8))).count() > 4);
+
+        let seen: Vec<Rc<RefCell<Seen>>> = (0..4).map(|_| Rc::new(RefCell::new(Seen::default()))).collect();
+        let config: GameConfig = GameConfig::new(counting_agents(&seen), Some(3), Some(12), false);
+        let mut env: EuchreEnv = EuchreEnv::from_moves(config, &DealSpec::new(), moves).unwrap();
+        let events: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(vec![]));
+        env.add_observer(Box::new(Recorder { events: Rc::clone(&events) }));
+        let rewards: Vec<u8> = env.resume();
+
+        assert_saw_every_hook(&env, &seen, &rewards);
+        assert_eq!(events.borrow().iter().filter(|x| x.starts_with("action")).count(), env.get_action_history().len());
+        assert_eq!(events.borrow().iter().filter(|x| x.starts_with("trick")).count(), env.game.get_tricks().len());
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let players: Vec<Box<dyn Agent>> = vec![Box::new(RandomAgent::new(None)),
+                                                Box::new(RandomAgent::new(None)),
+                                                Box::new(RandomAgent::new(None)),
+                                                Box::new(RandomAgent::new(None))];
+        let config: GameConfig = GameConfig::new(players, Some(0), Some(8), false);
+        let result = EuchreEnv::from_moves(config, &DealSpec::new(), vec![(1, Action::Pass), (2, Action::HAPlay)]);
+        assert!(result.is_err_and(|e| matches!(e, EuchreError::IllegalMove { index: 1, .. })));
+    }
 }
@@ -0,0 +1,28 @@
+use crate::action::Action;
+use crate::dealer::Deal;
+use crate::env::euchre_match::MatchResult;
+use crate::game::EuchreGame;
+use crate::game::trick::Trick;
+
+/// Reacts to the progress of the games played by an `EuchreEnv`, e.g. to collect stats or drive a UI.
+///
+/// Unlike agents, observers see every player's cards.
+/// Every callback does nothing by default, so implementors only override the ones they need.
+pub trait GameObserver {
+    /// A new hand was dealt by `dealer_id`.  
+    /// A game resumed after some moves is announced when the agents start playing it,
+    /// and its moves are then replayed through `on_action` and `on_trick_complete`
+    fn on_deal(&mut self, _dealer_id: u8, _deal: &Deal) {}
+
+    /// `player_id` took `action`, and `game` is the state right after it
+    fn on_action(&mut self, _player_id: u8, _action: Action, _game: &EuchreGame) {}
+
+    /// The last action completed `trick`
+    fn on_trick_complete(&mut self, _trick: &Trick) {}
+
+    /// The hand is over and each player earned `rewards[player_id]` points
+    fn on_hand_end(&mut self, _game: &EuchreGame, _rewards: &[u8]) {}
+
+    /// An `EuchreMatch` finished
+    fn on_match_end(&mut self, _result: &MatchResult) {}
+}
@@ -0,0 +1,788 @@
+//! A game server that lets players on other machines take seats over TCP.
+//!
+//! Every message is a single line holding a JSON object with a `type`.
+//! Cards and actions are written the way they are displayed, e.g. `"HJ"` and `"HJPlay"`.
+//!
+//! A client sends:
+//! 1. `{"type":"join"}` to take the first free seat, or `{"type":"join","seat":2}` to take seat 2
+//! 2. `{"type":"rejoin","token":"..."}` to take back its seat after losing its connection
+//! 3. `{"type":"action","action":"Pick"}` to answer the last state it was sent
+//!
+//! The server sends:
+//! 1. `{"type":"welcome","seat":2,"token":"..."}` after a join or rejoin. Keep the token to rejoin with.
+//! 2. `{"type":"hand_start","seat":2,"hand":["HJ",...]}` when a hand is dealt
+//! 3. `{"type":"state","state":{...}}` when it is the seat's turn, where the state is the seat's
+//!    `ScopedGameState`, including its `legal_actions`. It is sent again after a rejoin until it is answered.
+//! 4. `{"type":"action_taken","seat":1,"action":"Pass"}` for every action, including the seat's own.
+//!    Other players' discards aren't sent.
+//! 5. `{"type":"trick_end","winner":1,"cards":["H9",...]}` and `{"type":"hand_end","rewards":[2,0,2,0]}`
+//! 6. `{"type":"error","message":"..."}` when a message can't be accepted, e.g. an illegal action.
+//!    The connection stays open, so the client can try again.
+//!
+//! At most `ServerConfig::max_clients` clients are served at once; the server closes any more right away.
+//!
+//! A seat that is disconnected when it must act is waited for, up to `ServerConfig::reconnect_timeout`.
+//! After that its fallback agent acts for it until it rejoins, or the server panics if it has none.
+
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, Weak};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::agent::Agent;
+use crate::card::Card;
+use crate::game::scoped_state::ScopedGameState;
+use crate::utils::get_rdm_gen;
+
+/// How long a `GameServer` waits for its remote players.
+/// Change the defaults with struct update syntax:
+///
+/// ```
+/// use std::time::Duration;
+/// use euchre_rs::env::server::ServerConfig;
+///
+/// let config = ServerConfig { reconnect_timeout: None, ..ServerConfig::default() };
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ServerConfig {
+    /// How long a disconnected seat is waited for when it must act, `None` to wait forever
+    pub reconnect_timeout: Option<Duration>,
+    /// How long writing a message to a client may take before the client is disconnected
+    pub write_timeout: Duration,
+    /// Most clients served at once, including ones that haven't joined a seat
+    pub max_clients: usize,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            reconnect_timeout: Some(Duration::from_secs(60)),
+            write_timeout: Duration::from_secs(1),
+            max_clients: 16,
+        }
+    }
+}
+
+/// Who sits in a seat of a `GameServer`.
+pub enum Seat {
+    /// An agent in this process
+    Local(Box<dyn Agent>),
+    /// A player connecting over TCP
+    Remote {
+        /// Acts for the player when they stay disconnected past the reconnect timeout
+        fallback: Option<Box<dyn Agent>>,
+    },
+}
+
+impl Seat {
+    /// A remote seat without a fallback agent.
+    pub fn remote() -> Seat {
+        Seat::Remote { fallback: None }
+    }
+}
+
+impl fmt::Debug for Seat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Seat::Local(_) => write!(f, "Local"),
+            Seat::Remote { fallback } => write!(f, "Remote {{ fallback: {} }}", fallback.is_some()),
+        }
+    }
+}
+
+/// Messages sent to clients.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage<'a> {
+    Welcome { seat: u8, token: &'a str },
+    HandStart { seat: u8, hand: &'a [Card] },
+    State { state: &'a ScopedGameState },
+    ActionTaken { seat: u8, action: Action },
+    TrickEnd { winner: u8, cards: &'a [Card] },
+    HandEnd { rewards: &'a [u8] },
+    Error { message: String },
+}
+
+impl ServerMessage<'_> {
+    fn to_line(&self) -> String {
+        serde_json::to_string(self).expect("Server messages always serialize")
+    }
+}
+
+/// Messages read from clients.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage {
+    Join {
+        #[serde(default)]
+        seat: Option<u8>,
+    },
+    Rejoin { token: String },
+    Action { action: Action },
+}
+
+/// A client's connection to the seat it joined.
+struct Connection {
+    stream: TcpStream,
+    /// Held while writing to the client, shared with the thread serving it
+    writing: Arc<Mutex<()>>,
+    /// Tells this connection apart from earlier connections to the same seat
+    id: u64,
+}
+
+#[derive(Default)]
+struct SeatState {
+    remote: bool,
+    /// Given to the first client to join the seat, and needed to rejoin it
+    token: Option<String>,
+    connection: Option<Connection>,
+    /// The state line the seat must answer, and its legal actions
+    pending: Option<(String, Vec<Action>)>,
+    reply: Option<Action>,
+    /// Whether the seat stayed disconnected past the reconnect timeout, so it isn't waited for again
+    abandoned: bool,
+}
+
+impl SeatState {
+    /// Write `line` to the seat's client, if it is connected, disconnecting it if that fails.
+    fn send(&mut self, line: &str) {
+        let Some(connection) = self.connection.as_mut() else {
+            return
+        };
+        let written: io::Result<()> = {
+            let _writing: MutexGuard<()> = connection.writing.lock().unwrap();
+            writeln!(connection.stream, "{}", line)
+        };
+        if written.is_err() {
+            let _ = connection.stream.shutdown(Shutdown::Both);
+            self.connection = None;
+        }
+    }
+}
+
+struct TableState {
+    seats: Vec<SeatState>,
+    next_id: u64,
+    gen: ChaCha8Rng,
+}
+
+/// The seats of a server, shared by its remote agents and the threads serving its clients.
+struct Table {
+    state: Mutex<TableState>,
+    changed: Condvar,
+    config: ServerConfig,
+    /// The address the server listens on, connected to on drop to wake the thread accepting clients
+    address: SocketAddr,
+    /// Clients being served
+    clients: AtomicUsize,
+}
+
+impl Table {
+    fn lock(&self) -> MutexGuard<'_, TableState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Seat the client on `stream` in `wanted`, or the first free remote seat.
+    /// Returns the seat and connection id, or why the client can't be seated.
+    fn join(&self, wanted: Option<u8>, stream: &TcpStream, writing: &Arc<Mutex<()>>) -> Result<(usize, u64), String> {
+        let mut state: MutexGuard<TableState> = self.lock();
+        let free = |x: &SeatState| x.remote && x.token.is_none();
+        let seat: usize = match wanted {
+            Some(seat) if state.seats.get(usize::from(seat)).is_some_and(free) => usize::from(seat),
+            Some(seat) => return Err(format!("Seat {} is not free", seat)),
+            None => state.seats.iter().position(free).ok_or_else(|| String::from("No seats are free"))?,
+        };
+        let token: String = format!("{:016x}", state.gen.gen::<u64>());
+        state.seats[seat].token = Some(token);
+        self.connect(&mut state, seat, stream, writing)
+    }
+
+    /// Seat the client on `stream` in the seat `token` was given for.
+    fn rejoin(&self, token: &str, stream: &TcpStream, writing: &Arc<Mutex<()>>) -> Result<(usize, u64), String> {
+        let mut state: MutexGuard<TableState> = self.lock();
+        let seat: usize = state.seats.iter()
+            .position(|x| x.token.as_deref() == Some(token))
+            .ok_or_else(|| String::from("Unknown token"))?;
+        self.connect(&mut state, seat, stream, writing)
+    }
+
+    /// Make `stream` the connection of `seat`, closing any earlier one,
+    /// then welcome the client and send it the state it must answer, if any.
+    fn connect(&self, state: &mut TableState, seat: usize, stream: &TcpStream, writing: &Arc<Mutex<()>>) -> Result<(usize, u64), String> {
+        let stream: TcpStream = stream.try_clone().map_err(|e| e.to_string())?;
+        let id: u64 = state.next_id;
+        state.next_id += 1;
+
+        let entry: &mut SeatState = &mut state.seats[seat];
+        if let Some(old) = entry.connection.replace(Connection { stream, writing: Arc::clone(writing), id }) {
+            let _ = old.stream.shutdown(Shutdown::Both);
+        }
+        entry.abandoned = false;
+        let token: String = entry.token.clone().unwrap();
+        entry.send(&ServerMessage::Welcome { seat: seat as u8, token: &token }.to_line());
+        if let Some((line, _)) = entry.pending.clone() {
+            entry.send(&line);
+        }
+        self.changed.notify_all();
+        Ok((seat, id))
+    }
+
+    /// Answer the state `seat` was sent with `action`, if the connection `id` is still the seat's.
+    fn submit(&self, seat: usize, id: u64, action: Action) {
+        let mut state: MutexGuard<TableState> = self.lock();
+        let entry: &mut SeatState = &mut state.seats[seat];
+        if entry.connection.as_ref().map(|x| x.id) != Some(id) {
+            return
+        }
+        let error: String = match &entry.pending {
+            None => String::from("It is not your turn"),
+            Some((_, legal_actions)) if !legal_actions.contains(&action) => {
+                format!("{} is not one of the legal actions {:?}", action, legal_actions)
+            },
+            Some(_) => {
+                entry.pending = None;
+                entry.reply = Some(action);
+                self.changed.notify_all();
+                return
+            },
+        };
+        entry.send(&ServerMessage::Error { message: error }.to_line());
+    }
+
+    /// Forget the connection `id` of `seat`, if it is still the seat's.
+    fn disconnect(&self, seat: usize, id: u64) {
+        let mut state: MutexGuard<TableState> = self.lock();
+        if state.seats[seat].connection.as_ref().map(|x| x.id) == Some(id) {
+            state.seats[seat].connection = None;
+            self.changed.notify_all();
+        }
+    }
+
+    /// Send `state` to `seat` and wait for its answer.
+    /// Returns `None` if the seat stays disconnected past the reconnect timeout, or already did.
+    fn ask(&self, seat: usize, scoped: &ScopedGameState) -> Option<Action> {
+        let line: String = ServerMessage::State { state: scoped }.to_line();
+        let mut state: MutexGuard<TableState> = self.lock();
+        let entry: &mut SeatState = &mut state.seats[seat];
+        entry.reply = None;
+        entry.pending = Some((line.clone(), scoped.legal_actions.clone()));
+        entry.send(&line);
+
+        let mut gone_since: Option<Instant> = None;
+        loop {
+            let entry: &mut SeatState = &mut state.seats[seat];
+            if let Some(action) = entry.reply.take() {
+                return Some(action)
+            }
+            if entry.connection.is_some() {
+                gone_since = None;
+                state = self.changed.wait(state).unwrap();
+                continue
+            }
+
+            let since: Instant = *gone_since.get_or_insert_with(Instant::now);
+            let Some(limit) = self.config.reconnect_timeout else {
+                state = self.changed.wait(state).unwrap();
+                continue
+            };
+            if entry.abandoned || since.elapsed() >= limit {
+                entry.abandoned = true;
+                entry.pending = None;
+                return None
+            }
+            state = self.changed.wait_timeout(state, limit - since.elapsed()).unwrap().0;
+        }
+    }
+
+    /// Send `message` to `seat`, if it is connected.
+    fn push(&self, seat: usize, message: &ServerMessage) {
+        self.lock().seats[seat].send(&message.to_line());
+    }
+
+    /// Whether a client is connected to every remote seat.
+    fn is_full(&self) -> bool {
+        self.lock().seats.iter().all(|x| !x.remote || x.connection.is_some())
+    }
+}
+
+impl Drop for Table {
+    /// Close every connection, so the threads serving them stop,
+    /// and wake the thread accepting clients, so it sees the table is gone and stops too.
+    fn drop(&mut self) {
+        for seat in self.state.get_mut().unwrap().seats.iter() {
+            if let Some(connection) = &seat.connection {
+                let _ = connection.stream.shutdown(Shutdown::Both);
+            }
+        }
+
+        let mut address: SocketAddr = self.address;
+        if address.ip().is_unspecified() {
+            address.set_ip(if address.is_ipv4() {IpAddr::V4(Ipv4Addr::LOCALHOST)} else {IpAddr::V6(Ipv6Addr::LOCALHOST)});
+        }
+        let _ = TcpStream::connect_timeout(&address, Duration::from_secs(1));
+    }
+}
+
+/// Accept clients until the table is gone, serving each on its own thread.
+/// Clients past `ServerConfig::max_clients` are disconnected right away.
+fn accept(listener: TcpListener, table: Weak<Table>) {
+    for stream in listener.incoming() {
+        let Some(strong) = table.upgrade() else {
+            break
+        };
+        let Ok(stream) = stream else {
+            // Likely out of file descriptors, so give some clients time to leave
+            thread::sleep(Duration::from_millis(10));
+            continue
+        };
+        if strong.clients.fetch_add(1, Ordering::SeqCst) >= strong.config.max_clients {
+            strong.clients.fetch_sub(1, Ordering::SeqCst);
+            continue
+        }
+        let table: Weak<Table> = table.clone();
+        thread::spawn(move || {
+            serve(stream, &table);
+            if let Some(table) = table.upgrade() {
+                table.clients.fetch_sub(1, Ordering::SeqCst);
+            }
+        });
+    }
+}
+
+/// Write `message` to a client that may not have a seat, disconnecting it if that fails.
+/// `writing` is held meanwhile, so the message can't be interleaved with one sent to the client's seat.
+fn reply(stream: &TcpStream, writing: &Mutex<()>, message: &ServerMessage) {
+    let _writing: MutexGuard<()> = writing.lock().unwrap();
+    if writeln!(&*stream, "{}", message.to_line()).is_err() {
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+/// Read the messages of one client until it disconnects or the table is gone.
+fn serve(stream: TcpStream, table: &Weak<Table>) {
+    let Some(write_timeout) = table.upgrade().map(|x| x.config.write_timeout) else {
+        return
+    };
+    // Messages are small and answered one at a time, so send them without waiting to batch them
+    if stream.set_nodelay(true).and_then(|_| stream.set_write_timeout(Some(write_timeout))).is_err() {
+        return
+    }
+    let Ok(reader) = stream.try_clone() else {
+        return
+    };
+    let writing: Arc<Mutex<()>> = Arc::new(Mutex::new(()));
+    let mut seat: Option<(usize, u64)> = None;
+
+    for line in BufReader::new(reader).lines() {
+        let (Ok(line), Some(table)) = (line, table.upgrade()) else {
+            break
+        };
+        let error: Option<String> = match (seat, serde_json::from_str::<ClientMessage>(&line)) {
+            (_, Err(e)) => Some(format!("Not a message: {}", e)),
+            (Some(_), Ok(ClientMessage::Join { .. } | ClientMessage::Rejoin { .. })) => Some(String::from("Already seated")),
+            (None, Ok(ClientMessage::Action { .. })) => Some(String::from("Join a seat first")),
+            (Some((joined, id)), Ok(ClientMessage::Action { action })) => {
+                table.submit(joined, id, action);
+                None
+            },
+            (None, Ok(ClientMessage::Join { seat: wanted })) => table.join(wanted, &stream, &writing).map(|x| seat = Some(x)).err(),
+            (None, Ok(ClientMessage::Rejoin { token })) => table.rejoin(&token, &stream, &writing).map(|x| seat = Some(x)).err(),
+        };
+        drop(table);
+        if let Some(message) = error {
+            reply(&stream, &writing, &ServerMessage::Error { message });
+        }
+    }
+
+    if let (Some((joined, id)), Some(table)) = (seat, table.upgrade()) {
+        table.disconnect(joined, id);
+    }
+}
+
+/// The agent playing a remote seat, by pushing everything the seat may see to its client.
+/// Its fallback agent is shown the same, so it can take over at any point of the hand.
+struct RemoteAgent {
+    seat: usize,
+    table: Arc<Table>,
+    fallback: Option<Box<dyn Agent>>,
+}
+
+impl Agent for RemoteAgent {
+    /// Panics if the seat stays disconnected past the reconnect timeout and there is no fallback agent.
+    fn decide_action(&mut self, state: &ScopedGameState) -> Action {
+        match (self.table.ask(self.seat, state), &mut self.fallback) {
+            (Some(action), _) => action,
+            (None, Some(fallback)) => fallback.decide_action(state),
+            (None, None) => panic!("Seat {} didn't reconnect within {:?}", self.seat, self.table.config.reconnect_timeout),
+        }
+    }
+
+    fn on_hand_start(&mut self, seat: u8, hand: &[Card]) {
+        self.table.push(self.seat, &ServerMessage::HandStart { seat, hand });
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_start(seat, hand);
+        }
+    }
+
+    fn observe_action(&mut self, seat: u8, action: Action) {
+        self.table.push(self.seat, &ServerMessage::ActionTaken { seat, action });
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.observe_action(seat, action);
+        }
+    }
+
+    fn on_trick_end(&mut self, winner: u8, cards: &[Card]) {
+        self.table.push(self.seat, &ServerMessage::TrickEnd { winner, cards });
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_trick_end(winner, cards);
+        }
+    }
+
+    fn on_hand_end(&mut self, rewards: &[u8]) {
+        self.table.push(self.seat, &ServerMessage::HandEnd { rewards });
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.on_hand_end(rewards);
+        }
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        if let Some(fallback) = self.fallback.as_mut() {
+            fallback.reseed(seed);
+        }
+    }
+}
+
+/// A table of four seats, some of which are played over TCP, see the module docs for the protocol.
+///
+/// The server accepts clients from the moment it is bound until its agents are dropped.
+/// Play on it by building an `EuchreEnv` from its agents:
+///
+/// ```no_run
+/// use euchre_rs::agent::random::RandomAgent;
+/// use euchre_rs::env::{EuchreEnv, config::GameConfig};
+/// use euchre_rs::env::server::{GameServer, Seat, ServerConfig};
+///
+/// let seats = vec![Seat::remote(),
+///                  Seat::Local(Box::new(RandomAgent::new(None))),
+///                  Seat::remote(),
+///                  Seat::Local(Box::new(RandomAgent::new(None)))];
+/// let server = GameServer::bind("0.0.0.0:7878", seats, ServerConfig::default()).unwrap();
+/// server.wait_for_players(None).unwrap();
+/// let mut env = EuchreEnv::new(GameConfig::new(server.into_agents(), None, None, true));
+/// env.run();
+/// ```
+pub struct GameServer {
+    table: Arc<Table>,
+    agents: Vec<Box<dyn Agent>>,
+    address: SocketAddr,
+}
+
+impl GameServer {
+    /// Listen for clients on `address`, seating them in the remote `seats`.
+    /// Returns an error if there aren't exactly 4 seats or the address can't be bound.
+    pub fn bind(address: impl ToSocketAddrs, seats: Vec<Seat>, config: ServerConfig) -> io::Result<Self> {
+        if seats.len() != 4 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("A table needs 4 seats, not {}", seats.len())))
+        }
+        let listener: TcpListener = TcpListener::bind(address)?;
+        let address: SocketAddr = listener.local_addr()?;
+
+        let table: Arc<Table> = Arc::new(Table {
+            state: Mutex::new(TableState {
+                seats: seats.iter().map(|x| SeatState { remote: matches!(x, Seat::Remote { .. }), ..SeatState::default() }).collect(),
+                next_id: 0,
+                gen: get_rdm_gen(None),
+            }),
+            changed: Condvar::new(),
+            config,
+            address,
+            clients: AtomicUsize::new(0),
+        });
+        let agents: Vec<Box<dyn Agent>> = seats.into_iter().enumerate().map(|(seat, x)| match x {
+            Seat::Local(agent) => agent,
+            Seat::Remote { fallback } => Box::new(RemoteAgent { seat, table: Arc::clone(&table), fallback }) as Box<dyn Agent>,
+        }).collect();
+
+        let weak: Weak<Table> = Arc::downgrade(&table);
+        thread::spawn(move || accept(listener, weak));
+        Ok(GameServer { table, agents, address })
+    }
+
+    /// The address the server is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Block until a client is connected to every remote seat.
+    /// Returns a `TimedOut` error if that takes longer than `timeout`.
+    pub fn wait_for_players(&self, timeout: Option<Duration>) -> io::Result<()> {
+        let start: Instant = Instant::now();
+        let mut state: MutexGuard<TableState> = self.table.lock();
+        loop {
+            let empty: usize = state.seats.iter().filter(|x| x.remote && x.connection.is_none()).count();
+            if empty == 0 {
+                return Ok(())
+            }
+            state = match timeout {
+                None => self.table.changed.wait(state).unwrap(),
+                Some(limit) if start.elapsed() < limit => self.table.changed.wait_timeout(state, limit - start.elapsed()).unwrap().0,
+                Some(_) => return Err(io::Error::new(io::ErrorKind::TimedOut, format!("{} seats are still empty", empty))),
+            };
+        }
+    }
+
+    /// Whether a client is connected to every remote seat.
+    pub fn is_full(&self) -> bool {
+        self.table.is_full()
+    }
+
+    /// The agents for each seat, in seat order, to build a `GameConfig` with.
+    /// Remote seats keep being served for as long as their agents live.
+    pub fn into_agents(self) -> Vec<Box<dyn Agent>> {
+        self.agents
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Lines;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::agent::random::RandomAgent;
+    use crate::env::EuchreEnv;
+    use crate::env::config::GameConfig;
+
+    /// A client of the server, as another program would write it.
+    struct Client {
+        stream: TcpStream,
+        lines: Lines<BufReader<TcpStream>>,
+    }
+
+    impl Client {
+        fn connect(address: SocketAddr) -> Client {
+            let stream: TcpStream = TcpStream::connect(address).unwrap();
+            stream.set_read_timeout(Some(Duration::from_secs(10))).unwrap();
+            stream.set_nodelay(true).unwrap();
+            let lines: Lines<BufReader<TcpStream>> = BufReader::new(stream.try_clone().unwrap()).lines();
+            Client { stream, lines }
+        }
+
+        fn send(&mut self, line: &str) {
+            writeln!(self.stream, "{}", line).unwrap();
+        }
+
+        fn receive(&mut self) -> Value {
+            serde_json::from_str(&self.lines.next().unwrap().unwrap()).unwrap()
+        }
+
+        /// Join `seat`, returning the token.
+        fn join(&mut self, seat: u8) -> String {
+            self.send(&format!(r#"{{"type":"join","seat":{}}}"#, seat));
+            let welcome: Value = self.receive();
+            assert_eq!((welcome["type"].as_str(), welcome["seat"].as_u64()), (Some("welcome"), Some(u64::from(seat))));
+            welcome["token"].as_str().unwrap().to_string()
+        }
+
+        /// Skip messages until one of `kind`.
+        fn receive_until(&mut self, kind: &str) -> Value {
+            loop {
+                let message: Value = self.receive();
+                if message["type"] == kind {
+                    return message
+                }
+            }
+        }
+
+        /// Take the first legal action of every state until the hand ends.
+        /// Returns the actions taken and the hand's rewards.
+        fn play(&mut self) -> (Vec<Action>, Value) {
+            let mut actions: Vec<Action> = vec![];
+            loop {
+                let message: Value = self.receive();
+                match message["type"].as_str() {
+                    Some("state") => {
+                        let action: Value = message["state"]["legal_actions"][0].clone();
+                        actions.push(serde_json::from_value(action.clone()).unwrap());
+                        self.send(&format!(r#"{{"type":"action","action":{}}}"#, action));
+                    },
+                    Some("hand_end") => return (actions, message["rewards"].clone()),
+                    _ => {},
+                }
+            }
+        }
+    }
+
+    fn seats(fallback: bool) -> Vec<Seat> {
+        let remote = |seed: u64| if fallback {Seat::Remote { fallback: Some(Box::new(RandomAgent::new(Some(seed)))) }} else {Seat::remote()};
+        vec![remote(0), Seat::Local(Box::new(RandomAgent::new(Some(1)))), remote(2), Seat::Local(Box::new(RandomAgent::new(Some(3))))]
+    }
+
+    fn run(server: GameServer) -> EuchreEnv {
+        let mut env: EuchreEnv = EuchreEnv::new(GameConfig::new(server.into_agents(), Some(3), Some(4), false));
+        env.run();
+        env
+    }
+
+    #[test]
+    fn remote_players_play_a_hand() {
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats(false), ServerConfig::default()).unwrap();
+        let address: SocketAddr = server.local_addr();
+        let clients: Vec<thread::JoinHandle<(Vec<Action>, Value)>> = [0, 2].into_iter().map(|seat| thread::spawn(move || {
+            let mut client: Client = Client::connect(address);
+            client.join(seat);
+            let hand: Value = client.receive_until("hand_start");
+            assert_eq!(hand["hand"].as_array().unwrap().len(), 5);
+            client.play()
+        })).collect();
+
+        server.wait_for_players(Some(Duration::from_secs(10))).unwrap();
+        assert!(server.is_full());
+        let env: EuchreEnv = run(server);
+        for (seat, client) in [0u8, 2].into_iter().zip(clients) {
+            let (actions, rewards): (Vec<Action>, Value) = client.join().unwrap();
+            let taken: Vec<Action> = env.get_action_history().iter().filter(|(id, _)| *id == seat).map(|(_, x)| *x).collect();
+            assert_eq!(actions, taken);
+            assert_eq!(rewards, serde_json::to_value(env.game.get_rewards().unwrap()).unwrap());
+        }
+    }
+
+    #[test]
+    fn rejects_bad_messages() {
+        let seats: Vec<Seat> = vec![Seat::remote(), Seat::Local(Box::new(RandomAgent::new(None))),
+                                    Seat::Local(Box::new(RandomAgent::new(None))), Seat::Local(Box::new(RandomAgent::new(None)))];
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats, ServerConfig::default()).unwrap();
+        let mut first: Client = Client::connect(server.local_addr());
+        let mut second: Client = Client::connect(server.local_addr());
+        let error = |client: &mut Client, line: &str| {
+            client.send(line);
+            let message: Value = client.receive();
+            assert_eq!(message["type"], "error");
+            message["message"].as_str().unwrap().to_string()
+        };
+
+        assert_eq!(error(&mut first, r#"{"type":"join","seat":1}"#), "Seat 1 is not free");
+        assert_eq!(error(&mut first, r#"{"type":"action","action":"Pass"}"#), "Join a seat first");
+        assert!(error(&mut first, "hello").starts_with("Not a message"));
+        first.join(0);
+        assert_eq!(error(&mut first, r#"{"type":"join"}"#), "Already seated");
+        assert_eq!(error(&mut first, r#"{"type":"action","action":"Pass"}"#), "It is not your turn");
+        assert_eq!(error(&mut second, r#"{"type":"join"}"#), "No seats are free");
+        assert_eq!(error(&mut second, r#"{"type":"rejoin","token":"nope"}"#), "Unknown token");
+        assert!(GameServer::bind("127.0.0.1:0", vec![], ServerConfig::default()).is_err());
+    }
+
+    #[test]
+    fn rejoining_resends_the_state() {
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats(false), ServerConfig::default()).unwrap();
+        let address: SocketAddr = server.local_addr();
+        let mut partner: Client = Client::connect(address);
+        partner.join(2);
+        let player = thread::spawn(move || {
+            let mut client: Client = Client::connect(address);
+            let token: String = client.join(0);
+            let state: Value = client.receive_until("state");
+            drop(client);
+
+            let mut client: Client = Client::connect(address);
+            client.send(&format!(r#"{{"type":"rejoin","token":"{}"}}"#, token));
+            assert_eq!(client.receive()["seat"], 0);
+            assert_eq!(client.receive(), state);
+
+            // S9Discard is never legal before the dealer picks up
+            client.send(r#"{"type":"action","action":"S9Discard"}"#);
+            assert!(client.receive()["message"].as_str().unwrap().starts_with("S9Discard is not one of the legal actions"));
+            let action: &Value = &state["state"]["legal_actions"][0];
+            client.send(&format!(r#"{{"type":"action","action":{}}}"#, action));
+            client.play();
+        });
+        let partner = thread::spawn(move || partner.play());
+
+        server.wait_for_players(Some(Duration::from_secs(10))).unwrap();
+        let env: EuchreEnv = run(server);
+        player.join().unwrap();
+        partner.join().unwrap();
+        assert!(env.game.is_over());
+    }
+
+    #[test]
+    fn clients_that_never_read_are_dropped() {
+        // A client flooding the server with junk it never reads the answers to can't hold up the game
+        let config: ServerConfig = ServerConfig { write_timeout: Duration::from_millis(100), ..ServerConfig::default() };
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats(false), config).unwrap();
+        let address: SocketAddr = server.local_addr();
+        let flood = thread::spawn(move || {
+            let mut stream: TcpStream = TcpStream::connect(address).unwrap();
+            let junk: String = "junk\n".repeat(1000);
+            for _ in 0..1000 {
+                if stream.write_all(junk.as_bytes()).is_err() {
+                    break
+                }
+            }
+        });
+        let players: Vec<thread::JoinHandle<(Vec<Action>, Value)>> = [0, 2].into_iter().map(|seat| thread::spawn(move || {
+            let mut client: Client = Client::connect(address);
+            client.join(seat);
+            client.play()
+        })).collect();
+
+        server.wait_for_players(Some(Duration::from_secs(10))).unwrap();
+        let env: EuchreEnv = run(server);
+        assert!(env.game.is_over());
+        for player in players {
+            player.join().unwrap();
+        }
+        flood.join().unwrap();
+    }
+
+    #[test]
+    fn serves_a_limited_number_of_clients() {
+        let config: ServerConfig = ServerConfig { max_clients: 1, ..ServerConfig::default() };
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats(false), config).unwrap();
+        let mut first: Client = Client::connect(server.local_addr());
+        first.join(0);
+        let mut second: Client = Client::connect(server.local_addr());
+        assert!(!matches!(second.lines.next(), Some(Ok(_))));
+
+        // Once the server is gone it stops listening
+        let address: SocketAddr = server.local_addr();
+        drop(server);
+        let refused: bool = (0..100).any(|_| {
+            thread::sleep(Duration::from_millis(20));
+            TcpStream::connect(address).is_err()
+        });
+        assert!(refused);
+    }
+
+    #[test]
+    fn abandoned_seats_use_the_fallback() {
+        let config: ServerConfig = ServerConfig { reconnect_timeout: Some(Duration::from_millis(100)), ..ServerConfig::default() };
+        let server: GameServer = GameServer::bind("127.0.0.1:0", seats(true), config).unwrap();
+        assert!(server.wait_for_players(Some(Duration::from_millis(50))).is_err());
+
+        let mut clients: Vec<Client> = [0, 2].into_iter().map(|seat| {
+            let mut client: Client = Client::connect(server.local_addr());
+            client.join(seat);
+            client
+        }).collect();
+        server.wait_for_players(Some(Duration::from_secs(10))).unwrap();
+        clients.clear();
+
+        let start: Instant = Instant::now();
+        let env: EuchreEnv = run(server);
+        assert!(env.game.is_over());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
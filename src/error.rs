@@ -0,0 +1,99 @@
+use std::fmt;
+
+use crate::action::Action;
+use crate::card::Card;
+use crate::game::step_error::StepError;
+
+/// Every error that can be returned by this crate.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EuchreError {
+    /// A dealer id was not between 0 and 3 inclusive
+    InvalidDealer(u8),
+    /// A game was configured with a number of agents other than 4
+    WrongAgentCount(usize),
+    /// A match was set to be played to 0 points
+    InvalidTargetScore(u8),
+    /// A card with an unset suit or rank was used where a real card is needed
+    UnsetCard(Card),
+    /// An action that doesn't play or discard a card was used as a card
+    NoCardForAction(Action),
+    /// An integer outside of the action space was used as an action
+    NoActionForInteger(u8),
+    /// A `DealSpec` that can't be dealt
+    InvalidDeal(String),
+    /// A move in a list of moves to replay could not be taken
+    IllegalMove {
+        index: usize,
+        player_id: u8,
+        action: Action,
+        reason: String,
+    },
+    /// `EuchreGame::step` rejected an action
+    Step(StepError),
+    /// Text that could not be parsed into a card, action or snapshot
+    Parse(String),
+    /// An agent was configured with a setting outside of the values it can take
+    InvalidAgentConfig(String),
+    /// Tried to rewind a game to an action index past the number of actions taken
+    UndoOutOfRange {
+        index: usize,
+        actions_taken: usize,
+    },
+    /// Neural network layers whose sizes don't fit together or with the game
+    InvalidNetwork(String),
+}
+
+impl fmt::Display for EuchreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EuchreError::InvalidDealer(id) => write!(f, "Dealer ID must be between 0 and 3 inclusive, got {}", id),
+            EuchreError::WrongAgentCount(count) => write!(f, "Can only play euchre with exactly 4 players, got {}", count),
+            EuchreError::InvalidTargetScore(score) => write!(f, "A match must be played to at least 1 point, got {}", score),
+            EuchreError::UnsetCard(card) => write!(f, "Card {} has an unset suit or rank", card),
+            EuchreError::NoCardForAction(action) => write!(f, "The action {:?} has no mapping to a Card", action),
+            EuchreError::NoActionForInteger(val) => write!(f, "Integer {:?} has no corresponding Action.", val),
+            EuchreError::InvalidDeal(reason) => write!(f, "{}", reason),
+            EuchreError::IllegalMove { index, player_id, action, reason } => {
+                write!(f, "Move {} ({} by player {}) {}", index, action, player_id, reason)
+            },
+            EuchreError::Step(err) => write!(f, "{}", err),
+            EuchreError::Parse(reason) => write!(f, "{}", reason),
+            EuchreError::InvalidAgentConfig(reason) => write!(f, "{}", reason),
+            EuchreError::UndoOutOfRange { index, actions_taken } => {
+                write!(f, "Can not rewind to action {}, only {} actions have been taken", index, actions_taken)
+            },
+            EuchreError::InvalidNetwork(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for EuchreError {}
+
+impl From<StepError> for EuchreError {
+    fn from(err: StepError) -> Self {
+        EuchreError::Step(err)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::{Suit, Rank};
+
+    #[test]
+    fn error_messages() {
+        assert_eq!(EuchreError::InvalidDealer(4).to_string(), "Dealer ID must be between 0 and 3 inclusive, got 4");
+        assert_eq!(EuchreError::UnsetCard(Card::new(Suit::Unset, Rank::Ace)).to_string(), "Card ?A has an unset suit or rank");
+        assert_eq!(EuchreError::IllegalMove { index: 2, player_id: 1, action: Action::Pick, reason: String::from("is illegal") }.to_string(),
+                   "Move 2 (Pick by player 1) is illegal");
+    }
+
+    #[test]
+    fn from_step_error() {
+        let err: EuchreError = StepError::GameOver.into();
+        assert_eq!(err, EuchreError::Step(StepError::GameOver));
+        assert_eq!(err.to_string(), "The game is already over");
+    }
+}
@@ -4,13 +4,40 @@ use rand_chacha::ChaCha8Rng;
 use crate::player::Player;
 use crate::card::{Card, Suit};
 use crate::action::{Action, FlippedChoice};
-use crate::dealer::Dealer;
+use crate::dealer::{Deal, DealSpec, Dealer};
 use crate::judger::Judger;
+use crate::error::EuchreError;
+use crate::rules::RuleSet;
 use crate::utils::get_rdm_gen;
 
 pub mod scoped_state;
+pub mod snapshot;
+pub mod step_error;
+pub mod trick;
 use self::scoped_state::ScopedGameState;
+use self::snapshot::{GameSnapshot, RngState};
+use self::step_error::StepError;
+use self::trick::Trick;
 
+/// Everything `EuchreGame::undo` needs to reverse a single `step`.
+#[derive(Clone)]
+struct UndoRecord {
+    player_id: u8,
+    action: Action,
+    flipped_choice: Option<FlippedChoice>,
+    calling_player_id: Option<u8>,
+    alone_player_id: Option<u8>,
+    trump: Option<Suit>,
+    led_suit: Option<Suit>,
+    /// The order before this step, only if this step changed it
+    order: Option<Vec<u8>>,
+    /// Where a played or discarded card was in the player's hand
+    hand_index: Option<usize>,
+    /// The winner and cards of the trick this step completed, if any
+    completed_trick: Option<(u8, Vec<Card>)>,
+}
+
+#[derive(Clone)]
 pub struct EuchreGame {
     is_over: bool,
     scores: Option<Vec<u8>>,
@@ -18,59 +45,161 @@ pub struct EuchreGame {
     curr_player_id: u8,
     dealer_id: u8,
     judger: Judger,
+    rules: RuleSet,
+    deal: Deal,
 
     flipped_card: Card,
     flipped_choice: Option<FlippedChoice>,
     calling_player_id: Option<u8>,
+    alone_player_id: Option<u8>,
     previous_played: Vec<Vec<Card>>,
     center: Option<Vec<Card>>,
     order: Vec<u8>,
     trump: Option<Suit>,
     led_suit: Option<Suit>,
+    history: Vec<UndoRecord>,
 
     _rng_gen: ChaCha8Rng,
 }
 
 impl EuchreGame {
 
-    /// Sets up a new EuchreGame.
+    /// Sets up a new EuchreGame.  
+    /// Panics if `dealer_id` is greater than 3, see `try_new` for a fallible version.
     pub fn new(
             dealer_id: Option<u8>,
             seed: Option<u64>,
+            rules: RuleSet,
         ) -> EuchreGame {
+        Self::try_new(dealer_id, seed, rules).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Sets up a new EuchreGame.  
+    /// Returns an error if `dealer_id` is greater than 3.
+    pub fn try_new(
+            dealer_id: Option<u8>,
+            seed: Option<u64>,
+            rules: RuleSet,
+        ) -> Result<EuchreGame, EuchreError> {
+        Self::with_deal_spec(dealer_id, seed, rules, &DealSpec::new())
+    }
+
+    /// Sets up a new EuchreGame whose deal honors `spec`,
+    /// with the cards it leaves open dealt randomly.  
+    /// Returns an error if `spec` is contradictory or `dealer_id` is greater than 3.
+    pub fn with_deal_spec(
+            dealer_id: Option<u8>,
+            seed: Option<u64>,
+            rules: RuleSet,
+            spec: &DealSpec,
+        ) -> Result<EuchreGame, EuchreError> {
 
         let mut gen: ChaCha8Rng = get_rdm_gen(seed);
 
-        let deal_id = determine_dealer(dealer_id, &mut gen);
+        let deal_id = determine_dealer(dealer_id, &mut gen)?;
+        let deal: Deal = Dealer::new(&mut gen).deal_with_spec(spec)?;
+
+        Ok(Self::from_deal(deal_id, deal, rules, gen))
+    }
+
+    /// Sets up a new EuchreGame whose deal honors `spec`, then replays `moves` on it.
+    /// Each move is the id of the player taking the action, and the action taken.  
+    /// Returns an error naming the index of the first move that isn't legal when it's replayed.
+    pub fn from_moves(
+            dealer_id: Option<u8>,
+            seed: Option<u64>,
+            rules: RuleSet,
+            spec: &DealSpec,
+            moves: &[(u8, Action)],
+        ) -> Result<EuchreGame, EuchreError> {
+
+        let mut game: EuchreGame = Self::with_deal_spec(dealer_id, seed, rules, spec)?;
+        game.replay(moves)?;
+        Ok(game)
+    }
+
+    /// Rebuild a game from a snapshot taken with `snapshot`.  
+    /// Returns an error if the snapshot's deal or moves are invalid.
+    pub fn restore(snapshot: &GameSnapshot) -> Result<EuchreGame, EuchreError> {
+        if snapshot.dealer_id > 3 {
+            return Err(EuchreError::InvalidDealer(snapshot.dealer_id))
+        }
+        snapshot.deal.validate()?;
+
+        let mut game: EuchreGame = Self::from_deal(snapshot.dealer_id, snapshot.deal.clone(), snapshot.rules, snapshot.rng.to_rng());
+        game.replay(&snapshot.moves)?;
+        Ok(game)
+    }
+
+    /// Take a snapshot of the full game, which `restore` turns back into an identical game.
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot {
+            rules: self.rules,
+            dealer_id: self.dealer_id,
+            deal: self.deal.clone(),
+            moves: self.get_action_history(),
+            rng: RngState::of(&self._rng_gen),
+        }
+    }
+
+    /// Step through `moves`, checking each is taken in turn and is legal.
+    fn replay(&mut self, moves: &[(u8, Action)]) -> Result<(), EuchreError> {
+        let game: &mut EuchreGame = self;
+        for (i, (player_id, action)) in moves.iter().enumerate() {
+            let illegal = |reason: String| EuchreError::IllegalMove {
+                index: i,
+                player_id: *player_id,
+                action: *action,
+                reason,
+            };
+            if game.is_over() {
+                return Err(illegal(String::from("was made after the game was over")));
+            }
+            if *player_id != game.get_curr_player_id() {
+                return Err(illegal(format!("was made out of turn, it is player {}'s turn", game.get_curr_player_id())));
+            }
+            let legals: Vec<Action> = game.get_legal_actions();
+            if !legals.contains(action) {
+                return Err(illegal(format!("is illegal, legal actions are {:?}", legals)));
+            }
+            game.step(*action)?;
+        }
+        Ok(())
+    }
+
+    /// Sets up a new EuchreGame from cards that have already been dealt.
+    fn from_deal(deal_id: u8, deal: Deal, rules: RuleSet, gen: ChaCha8Rng) -> EuchreGame {
         let curr_p_id = (deal_id + 1) % 4;
-        let mut dealer: Dealer = Dealer::new(&mut gen);
-        dealer.shuffle();
 
         let mut players: Vec<Player> = vec![Player::new(0), 
                                         Player::new(1),
                                         Player::new(2),
                                         Player::new(3)];
         
-        for player in &mut players {
-            dealer.deal_cards(player);
+        for (player, hand) in players.iter_mut().zip(&deal.hands) {
+            player.add_cards(&mut hand.clone());
         }
 
         EuchreGame {
             is_over: false,
             scores: None,
-            players: players,
+            players,
             curr_player_id: curr_p_id,
             dealer_id: deal_id,
-            judger: Judger::new(),
+            judger: Judger::with_rules(rules),
+            rules,
 
-            flipped_card: dealer.flip_top_card(),
+            flipped_card: deal.flipped_card,
+            deal,
             flipped_choice: None,
             calling_player_id: None,
+            alone_player_id: None,
             previous_played: vec![vec![], vec![], vec![], vec![]],
             center: None,
-            order: Self::order_starting_from(curr_p_id),
+            order: Self::order_starting_from(curr_p_id, None),
             trump: None,
             led_suit: None,
+            history: vec![],
 
             _rng_gen: gen,
         }
@@ -80,6 +209,28 @@ impl EuchreGame {
         self.is_over
     }
 
+    /// Returns whether the hand ended because every player passed twice.
+    /// Only possible when the rules don't stick the dealer.
+    pub fn is_misdeal(&self) -> bool {
+        self.is_over && self.trump.is_none()
+    }
+
+    /// Returns every card as it was originally dealt, before any were picked up or played
+    pub fn get_deal(&self) -> &Deal {
+        &self.deal
+    }
+
+    /// Returns the cards every player holds right now, indexed by player id.  
+    /// Unlike `get_state`, this reveals every hand.
+    pub fn get_hands(&self) -> Vec<Vec<Card>> {
+        self.players.iter().map(|x| x.hand_clone()).collect()
+    }
+
+    /// Returns the rules this hand is played with
+    pub fn get_rules(&self) -> RuleSet {
+        self.rules
+    }
+
     /// Get the current game state as the current player sees it.
     pub fn get_state(&self) -> ScopedGameState {
         let legals: Vec<Action> = self.get_legal_actions();
@@ -88,6 +239,7 @@ impl EuchreGame {
             current_actor: self.curr_player_id,
             hand: self.imm_player_ref(self.curr_player_id).hand_clone(),
             calling_actor: self.calling_player_id,
+            alone_actor: self.alone_player_id,
             dealer_actor: self.dealer_id,
             flipped_card: self.flipped_card,
             flipped_choice: self.flipped_choice,
@@ -97,32 +249,165 @@ impl EuchreGame {
             order: self.order.clone(),
             center: self.center.clone(),
             previous_played: self.previous_played.clone(),
+            discarded: self.history.iter()
+                .find(|x| x.player_id == self.curr_player_id && x.action.is_discard())
+                .and_then(|x| Action::action_to_card(x.action).ok()),
             legal_actions: legals,
+            rules: self.rules,
         }
     }
 
     /// Update the game state based on the passed in `Action`.
-    /// Returns the scoped game state and the current player's id
-    pub fn step(&mut self, action: Action) -> (ScopedGameState, u8){
-        if action == Action::Pick { // Pick
-            self.perform_pick_action();
+    /// Returns the scoped game state and the current player's id,
+    /// or an error explaining why the action can't be taken, leaving the game unchanged.
+    pub fn step(&mut self, action: Action) -> Result<(ScopedGameState, u8), StepError> {
+        self.check_action(action)?;
+
+        let mut record: UndoRecord = UndoRecord {
+            player_id: self.curr_player_id,
+            action,
+            flipped_choice: self.flipped_choice,
+            calling_player_id: self.calling_player_id,
+            alone_player_id: self.alone_player_id,
+            trump: self.trump,
+            led_suit: self.led_suit,
+            order: None,
+            hand_index: None,
+            completed_trick: None,
+        };
+
+        if action == Action::Pick || action == Action::PickAlone { // Pick
+            record.order = Some(self.order.clone());
+            self.perform_pick_action(action.is_alone());
         } else if action == Action::Pass { // Pass
             self.perform_pass_action();
-        } else if (action as u8) < 6 { // Call
+        } else if (action as u8) < 6 || action.is_alone() { // Call
+            record.order = Some(self.order.clone());
             self.perform_call_action(action);
         } else if (action as u8) > 29 { // Discard
-            self.perform_discard_action(action);
+            record.hand_index = Some(self.perform_discard_action(action));
         } else {// Play
-            self.perform_play_card(action);
+            record.hand_index = Some(self.perform_play_card(action));
 
-            if self.center.as_ref().unwrap().len() == 4 {
+            if self.center.as_ref().unwrap().len() == self.order.len() {
+                record.order = Some(self.order.clone());
+                let cards: Vec<Card> = self.center.clone().unwrap();
                 self.end_trick();
+                record.completed_trick = Some((self.curr_player_id, cards));
                 self.decide_is_over();
             }
         }
+        self.history.push(record);
+
         let curr_id: u8 = self.get_curr_player_id();
         let state: ScopedGameState = self.get_state();
-        (state, curr_id)
+        Ok((state, curr_id))
+    }
+
+    /// Reverse the last successful `step`, returning the id of the player who took it and the action.  
+    /// Returns `None` if no actions have been taken.
+    pub fn undo(&mut self) -> Option<(u8, Action)> {
+        let record: UndoRecord = self.history.pop()?;
+        let action: Action = record.action;
+
+        self.is_over = false;
+        self.scores = None;
+        if let Some((winner_id, cards)) = record.completed_trick {
+            self.player_ref(winner_id).revoke_trick();
+            self.center = Some(cards);
+        }
+        if let Some(order) = record.order {
+            self.order = order;
+        }
+
+        if action == Action::Pick || action == Action::PickAlone {
+            if Some(self.dealer_id) != self.sitting_out_id() {
+                let flipped: Card = self.flipped_card;
+                self.player_ref(self.dealer_id).hand_ref().retain(|x| x != &flipped);
+            }
+        } else if (6..=53).contains(&(action as u8)) { // Play or Discard
+            let card: Card = Action::action_to_card(action).unwrap();
+            if (action as u8) < 30 {
+                let center: &mut Vec<Card> = self.center.as_mut().unwrap();
+                center.pop();
+                if center.is_empty() {
+                    self.center = None;
+                }
+                self.previous_played[usize::from(record.player_id)].pop();
+            }
+            self.player_ref(record.player_id).hand_ref().insert(record.hand_index.unwrap(), card);
+        }
+
+        self.curr_player_id = record.player_id;
+        self.flipped_choice = record.flipped_choice;
+        self.calling_player_id = record.calling_player_id;
+        self.alone_player_id = record.alone_player_id;
+        self.trump = record.trump;
+        self.led_suit = record.led_suit;
+
+        Some((record.player_id, action))
+    }
+
+    /// Reverse steps until only the first `n` actions of the game remain.  
+    /// Returns an error if fewer than `n` actions have been taken.
+    pub fn undo_to(&mut self, n: usize) -> Result<(), EuchreError> {
+        if n > self.history.len() {
+            return Err(EuchreError::UndoOutOfRange { index: n, actions_taken: self.history.len() })
+        }
+        while self.history.len() > n {
+            self.undo();
+        }
+        Ok(())
+    }
+
+    /// Returns every action taken so far, along with the id of the player who took it.
+    pub fn get_action_history(&self) -> Vec<(u8, Action)> {
+        self.history.iter().map(|x| (x.player_id, x.action)).collect()
+    }
+
+    /// Returns every trick completed so far, in the order they were played.
+    pub fn get_tricks(&self) -> Vec<Trick> {
+        self.history.iter().filter_map(Self::trick_of).collect()
+    }
+
+    /// Returns the trick completed by the most recent action,
+    /// or `None` if that action didn't complete a trick.
+    pub fn get_last_trick(&self) -> Option<Trick> {
+        self.history.last().and_then(Self::trick_of)
+    }
+
+    fn trick_of(record: &UndoRecord) -> Option<Trick> {
+        let (winner, cards) = record.completed_trick.clone()?;
+        Some(Trick { winner, order: record.order.clone().unwrap(), cards })
+    }
+
+    /// Returns an error explaining why the current player can't take `action`, if they can't.
+    fn check_action(&self, action: Action) -> Result<(), StepError> {
+        if self.is_over {
+            return Err(StepError::GameOver)
+        }
+        let legals: Vec<Action> = self.get_legal_actions();
+        if legals.contains(&action) {
+            return Ok(())
+        }
+
+        // Only a play or discard that matches the phase of the game gets a more specific error
+        let is_play = |x: &Action| (6..=29).contains(&(*x as u8));
+        let is_discard = |x: &Action| (30..=53).contains(&(*x as u8));
+        let in_phase: bool = (is_play(&action) && legals.iter().any(is_play))
+                             || (is_discard(&action) && legals.iter().any(is_discard));
+        if !in_phase {
+            return Err(StepError::IllegalAction(action))
+        }
+
+        let card: Card = Action::action_to_card(action).unwrap();
+        if !self.imm_player_ref(self.curr_player_id).hand_clone().contains(&card) {
+            Err(StepError::CardNotInHand(card))
+        } else if let Some(led_suit) = self.led_suit {
+            Err(StepError::MustFollowSuit { card, led_suit })
+        } else {
+            Err(StepError::IllegalAction(action))
+        }
     }
 
     /// Return the id of the current player
@@ -130,18 +415,26 @@ impl EuchreGame {
         self.curr_player_id
     }
 
+    /// Return the id of the player who dealt this hand
+    pub fn get_dealer_id(&self) -> u8 {
+        self.dealer_id
+    }
+
     /// Return all actions that the current player may
     /// select based on the game state.
     pub fn get_legal_actions(&self) -> Vec<Action> {
         let hand: Vec<Card> = self.imm_player_ref(self.curr_player_id).hand_clone();
         let mut actions: Vec<Action> = vec![];
         if hand.len() == 6 { // dealer must discard
-            actions = hand.iter().map(|x| Action::card_to_action(x, false)).collect();
+            actions = Self::card_actions(&hand, false);
 
         } else if self.trump.is_none() { // deciding trump
             if self.flipped_choice.is_none() { // flipped_card available
                 actions = vec![Action::Pick, Action::Pass];
-            } else if self.flipped_choice.unwrap() == FlippedChoice::TurnedDown { // no flipped card, dealer can't pass
+                if self.rules.allow_alone {
+                    actions.push(Action::PickAlone);
+                }
+            } else if self.flipped_choice == Some(FlippedChoice::TurnedDown) { // no flipped card, dealer may have to call
                 let turned_down_suit: u8 = match self.flipped_card.suit() {
                     Suit::Hearts => 0,
                     Suit::Diamonds => 1,
@@ -150,31 +443,40 @@ impl EuchreGame {
                     Suit::Unset => panic!("Unset Suit should be impossible here."),
                 };
                 let mut call_suits: Vec<Action> = vec![Action::CallH, Action::CallD, Action::CallS, Action::CallC];
+                let mut alone_suits: Vec<Action> = vec![Action::CallHAlone, Action::CallDAlone, Action::CallSAlone, Action::CallCAlone];
                 call_suits.remove(usize::from(turned_down_suit));
+                alone_suits.remove(usize::from(turned_down_suit));
                 actions = call_suits;
-                if self.get_curr_player_id() != self.dealer_id {
+                if self.get_curr_player_id() != self.dealer_id || !self.rules.stick_the_dealer {
                     actions.push(Action::Pass);
                 }
+                if self.rules.allow_alone {
+                    actions.append(&mut alone_suits);
+                }
             }
 
-        } else if self.led_suit.is_none() { // lead the trick
-            actions = hand.iter().map(|x| Action::card_to_action(x, true)).collect();
-        } else { // play given a led suit
-            let (t_trump, t_led) = (self.trump.unwrap(), self.led_suit.unwrap());
-            let t_acts: Vec<Action> = hand.iter()
-                          .filter(|x| (x.suit() == t_led && !x.is_left(t_trump))
-                                           || (x.suit() == t_trump && x.is_left(t_trump)))
-                          .map(|x| Action::card_to_action(x, true)).collect();
-
-            if t_acts.len() > 0 { // can follow suit
-                actions = t_acts;
+        } else if let Some(t_led) = self.led_suit { // play given a led suit
+            let t_trump: Suit = self.trump.unwrap();
+            let t_cards: Vec<Card> = hand.iter()
+                          .filter(|x| x.effective_suit(t_trump) == t_led)
+                          .copied().collect();
+
+            if !t_cards.is_empty() { // can follow suit
+                actions = Self::card_actions(&t_cards, true);
             } else { // can't follow suit
-                actions = hand.iter().map(|x| Action::card_to_action(x, true)).collect();
+                actions = Self::card_actions(&hand, true);
             }
+        } else { // lead the trick
+            actions = Self::card_actions(&hand, true);
         }
         actions
     }
 
+    /// Map every card to its play or discard action.
+    fn card_actions(cards: &[Card], is_play: bool) -> Vec<Action> {
+        cards.iter().filter_map(|x| Action::card_to_action(x, is_play).ok()).collect()
+    }
+
     /// Get rewards for the players
     /// Will only return not None if the game is over.
     pub fn get_rewards(&self) -> Option<Vec<u8>> {
@@ -182,14 +484,28 @@ impl EuchreGame {
     }
 
     // player MUST be either 0, 1, 2, or 3
-    fn order_starting_from(player: u8) -> Vec<u8> {
-        vec![player, (player + 1)%4, (player+2)%4, (player+3)%4]
+    // the `sitting_out` player, if any, is left out of the order
+    fn order_starting_from(player: u8, sitting_out: Option<u8>) -> Vec<u8> {
+        vec![player, (player + 1)%4, (player+2)%4, (player+3)%4].into_iter()
+            .filter(|x| Some(*x) != sitting_out)
+            .collect()
+    }
+
+    /// Return the id of the partner of a player going alone, who sits out the hand.
+    fn sitting_out_id(&self) -> Option<u8> {
+        self.alone_player_id.map(|x| (x + 2) % 4)
+    }
+
+    /// Calculate and return the id of the first player, starting with and going left of `player`,
+    /// who isn't sitting out this hand.
+    fn next_active_from(&self, player: u8) -> u8 {
+        if Some(player) == self.sitting_out_id() {(player + 1) % 4} else {player}
     }
 
     /// Calculate and return the id of the player whose
     /// turn is next, after current_player
     fn see_next_player(&self) -> u8 {
-        (self.curr_player_id + 1) % 4
+        self.next_active_from((self.curr_player_id + 1) % 4)
     }
 
     /// updates `current_player_id` to hold id of player
@@ -206,27 +522,42 @@ impl EuchreGame {
         &self.players[usize::from(id)]
     }
 
-    /// Changes game state to reflect taking the `Pick` action.  
-    /// 1. Dealer has `flipped_card` added to hand
+    /// Changes game state to reflect taking the `Pick` or `PickAlone` action.  
+    /// 1. Dealer has `flipped_card` added to hand, unless the dealer sits out
     /// 2. Trump is set
-    /// 3. Current player is changed to be the dealer
+    /// 3. Current player is changed to be the dealer, or the player left of the dealer if they sit out
     /// 4. `flipped_choice` is set to `PickedUp`
-    /// 5. The player who ordered up trump is recorded
-    fn perform_pick_action(&mut self) {
-        let flipped: Card = self.flipped_card.clone();
-        let dealer_player: &mut Player = self.player_ref(self.dealer_id);
-        dealer_player.hand_ref().push(flipped);
+    /// 5. The player who ordered up trump is recorded, and if they are going alone
+    fn perform_pick_action(&mut self, alone: bool) {
         self.trump = Some(self.flipped_card.suit());
         self.flipped_choice = Some(FlippedChoice::PickedUp);
         self.calling_player_id = Some(self.curr_player_id);
-        self.curr_player_id = self.dealer_id;
+        if alone {
+            self.alone_player_id = Some(self.curr_player_id);
+        }
+        let leader: u8 = self.next_active_from((self.dealer_id + 1) % 4);
+        self.order = Self::order_starting_from(leader, self.sitting_out_id());
+
+        if Some(self.dealer_id) == self.sitting_out_id() {
+            self.curr_player_id = leader;
+        } else {
+            let flipped: Card = self.flipped_card;
+            let dealer_player: &mut Player = self.player_ref(self.dealer_id);
+            dealer_player.hand_ref().push(flipped);
+            self.curr_player_id = self.dealer_id;
+        }
     } 
 
     /// Changes game state to reflect taking the `Pass` action.
     /// 1. Increment current player id by 1
     /// 2. If the player was the dealer, turn down the flipped card.
+    /// 3. If the dealer passed a second time, the hand is over with no points scored.
     fn perform_pass_action(&mut self) {
         if self.curr_player_id == self.dealer_id {
+            if self.flipped_choice.is_some() {
+                self.is_over = true;
+                self.scores = Some(vec![0, 0, 0, 0]);
+            }
             self.flipped_choice = Some(FlippedChoice::TurnedDown);
         }
         self.increment_player();
@@ -234,36 +565,34 @@ impl EuchreGame {
 
     /// Changes game state to reflect taking a `Call` action.  
     /// 1. Sets trump to suit that is called
-    /// 2. Sets current player to player left of dealer
-    /// 3. Sets calling_player to the current player
+    /// 2. Sets calling_player to the current player, and records if they are going alone
+    /// 3. Sets current player to player left of dealer, skipping a player who sits out
     fn perform_call_action(&mut self, action: Action) {
-        let trump: Suit = match action {
-            Action::CallH => Suit::Hearts,
-            Action::CallC => Suit::Clubs,
-            Action::CallD => Suit::Diamonds,
-            Action::CallS => Suit::Spades,
-            _ => {panic!("Invalid action to perform call action: {:?}", action)}
-        };
+        let trump: Suit = action.called_suit()
+            .unwrap_or_else(|| panic!("Invalid action to perform call action: {:?}", action));
         self.trump = Some(trump);
         self.calling_player_id = Some(self.curr_player_id);
-        self.curr_player_id = (self.dealer_id + 1) % 4;
+        if action.is_alone() {
+            self.alone_player_id = Some(self.curr_player_id);
+        }
+        self.curr_player_id = self.next_active_from((self.dealer_id + 1) % 4);
+        self.order = Self::order_starting_from(self.curr_player_id, self.sitting_out_id());
     }
 
     /// Changes game state to reflect taking a `Discard` action.
     /// 1. set current player to player left of dealer (also current player)
     /// 2. Remove specified card from players hand
-    fn perform_discard_action(&mut self, action: Action) {
+    /// 
+    /// Returns where the card was in the player's hand.
+    fn perform_discard_action(&mut self, action: Action) -> usize {
         let card_to_drop: Card = Action::action_to_card(action).unwrap();
     
         let player: &mut Player = self.player_ref(self.curr_player_id);
-        for (i, card) in player.hand_ref().iter().enumerate() {
-            if card == &card_to_drop {
-                player.hand_ref().remove(i);
-                break
-            }
-        }
+        let index: usize = player.hand_ref().iter().position(|x| x == &card_to_drop).unwrap();
+        player.hand_ref().remove(index);
 
         self.increment_player();
+        index
     }
 
     /// Changes game state to reflect taking a `Play` action.  
@@ -271,25 +600,18 @@ impl EuchreGame {
     /// 2. removes played card from players hand
     /// 3. adds played card to center
     /// 4. if first played card, sets led_suit
-    fn perform_play_card(&mut self, action: Action) {
+    /// 
+    /// Returns where the card was in the player's hand.
+    fn perform_play_card(&mut self, action: Action) -> usize {
         let card_to_play: Card = Action::action_to_card(action).unwrap();
 
         let player: &mut Player = self.player_ref(self.curr_player_id);
-        for (i, card) in player.hand_ref().iter().enumerate() {
-            if card == &card_to_play {
-                player.hand_ref().remove(i);
-                break
-            }
-        }
+        let index: usize = player.hand_ref().iter().position(|x| x == &card_to_play).unwrap();
+        player.hand_ref().remove(index);
 
         if self.center.is_none() {
             self.center = Some(vec![]);
-            self.led_suit = 
-                if card_to_play.is_left(self.trump.unwrap()) {
-                    Some(self.trump.unwrap())
-                } else {
-                    Some(card_to_play.suit())
-                };
+            self.led_suit = Some(card_to_play.effective_suit(self.trump.unwrap()));
         }
 
         self.center.as_mut().unwrap().push(card_to_play);
@@ -297,6 +619,7 @@ impl EuchreGame {
         self.previous_played[usize::from(self.curr_player_id)].push(card_to_play);
 
         self.increment_player();
+        index
     }
 
     /// judges the center cards and increments players trick counts.
@@ -312,7 +635,7 @@ impl EuchreGame {
         self.curr_player_id = winner_id;
 
         self.center = None;
-        self.order = Self::order_starting_from(winner_id);
+        self.order = Self::order_starting_from(winner_id, self.sitting_out_id());
         self.led_suit = None;
 
     }
@@ -322,11 +645,12 @@ impl EuchreGame {
     /// 1. If the current player has no cards in their hand
     /// 2. Assumes that this function is only called at the end of a trick
     fn decide_is_over(&mut self) {
-        if self.imm_player_ref(self.curr_player_id).hand_clone().len() == 0 {
+        if self.imm_player_ref(self.curr_player_id).hand_clone().is_empty() {
             self.is_over = true;
             self.scores = Some(self.judger.judge_round(
                                         self.get_player_tricks(),
-                                        self.calling_player_id.unwrap()));
+                                        self.calling_player_id.unwrap(),
+                                        self.alone_player_id.is_some()));
         }
     }
 
@@ -339,50 +663,373 @@ impl EuchreGame {
     }
 }
 
-/// Either return the passed in `Some(u8)`, panicing if greater than 3, 
+/// Either return the passed in `Some(u8)`, erroring if greater than 3, 
 /// or randomly choose a number on the range \[0,3\]
-fn determine_dealer(deal_id: Option<u8>, gen: &mut ChaCha8Rng) -> u8 {
+fn determine_dealer(deal_id: Option<u8>, gen: &mut ChaCha8Rng) -> Result<u8, EuchreError> {
     if let Some(num) = deal_id {
-        assert!(num < 4);
-        return num
+        if num > 3 {
+            return Err(EuchreError::InvalidDealer(num))
+        }
+        return Ok(num)
     }
-    gen.gen_range(0..=3)
+    Ok(gen.gen_range(0..=3))
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::card::Rank;
     use rand::prelude::*;
 
     #[test]
     fn create_game() {
-        let game: EuchreGame = EuchreGame::new(Some(0), Some(10));
+        let game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
     
         assert!(!game.is_over())
     }
 
+    #[test]
+    fn create_game_from_spec() {
+        let hand: Vec<Card> = vec![Card::new(Suit::Hearts, Rank::Jack), Card::new(Suit::Diamonds, Rank::Jack)];
+        let spec: DealSpec = DealSpec::new()
+            .hand(2, &hand)
+            .flipped(Card::new(Suit::Hearts, Rank::Ace));
+        let game: EuchreGame = EuchreGame::with_deal_spec(Some(0), Some(3), RuleSet::default(), &spec).unwrap();
+
+        assert_eq!(&game.imm_player_ref(2).hand_clone()[0..2], &hand[..]);
+        assert_eq!(game.get_state().flipped_card, Card::new(Suit::Hearts, Rank::Ace));
+    }
+
+    #[test]
+    fn create_game_from_bad_spec() {
+        let jack: Card = Card::new(Suit::Hearts, Rank::Jack);
+        let spec: DealSpec = DealSpec::new().hand(2, &[jack]).flipped(jack);
+        assert!(EuchreGame::with_deal_spec(Some(0), Some(3), RuleSet::default(), &spec).is_err());
+    }
+
+    #[test]
+    fn replay_moves() {
+        let moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pass), (3, Action::Pick)];
+        let game: EuchreGame = EuchreGame::from_moves(Some(0), Some(10), RuleSet::default(), &DealSpec::new(), &moves).unwrap();
+
+        assert_eq!(game.get_curr_player_id(), 0);
+        assert_eq!(game.get_state().calling_actor, Some(3));
+        assert!(game.get_legal_actions().iter().all(|x| (*x as u8) > 29));
+    }
+
+    #[test]
+    fn replay_full_game() {
+        let mut game: EuchreGame = EuchreGame::new(Some(1), Some(4), RuleSet::default());
+        let mut moves: Vec<(u8, Action)> = vec![];
+        while !game.is_over() {
+            let action: Action = *game.get_legal_actions().last().unwrap();
+            moves.push((game.get_curr_player_id(), action));
+            game.step(action).unwrap();
+        }
+
+        let replayed: EuchreGame = EuchreGame::from_moves(Some(1), Some(4), RuleSet::default(), &DealSpec::new(), &moves).unwrap();
+        assert!(replayed.is_over());
+        assert_eq!(replayed.get_rewards(), game.get_rewards());
+    }
+
+    #[test]
+    fn replay_illegal_moves() {
+        let spec: DealSpec = DealSpec::new();
+        let rules: RuleSet = RuleSet::default();
+
+        let err: String = EuchreGame::from_moves(Some(0), Some(10), rules, &spec, &[(1, Action::Pass), (2, Action::CallH)]).err().unwrap().to_string();
+        assert_eq!(err, "Move 1 (CallH by player 2) is illegal, legal actions are [Pick, Pass, PickAlone]");
+
+        let err: String = EuchreGame::from_moves(Some(0), Some(10), rules, &spec, &[(1, Action::Pass), (3, Action::Pass)]).err().unwrap().to_string();
+        assert_eq!(err, "Move 1 (Pass by player 3) was made out of turn, it is player 2's turn");
+
+        let mut moves: Vec<(u8, Action)> = vec![(1, Action::Pass), (2, Action::Pass), (3, Action::Pass), (0, Action::Pass),
+                                                (1, Action::Pass), (2, Action::Pass), (3, Action::Pass)];
+        moves.push((0, Action::Pass));
+        let err: EuchreError = EuchreGame::from_moves(Some(0), Some(10), rules, &spec, &moves).err().unwrap();
+        assert!(matches!(err, EuchreError::IllegalMove { index: 7, player_id: 0, action: Action::Pass, .. }));
+    }
+
+    fn cards(names: &[(Suit, Rank)]) -> Vec<Card> {
+        names.iter().map(|(suit, rank)| Card::new(*suit, *rank)).collect()
+    }
+
+    /// Dealer 0 holds all clubs but the jack, diamonds flipped.
+    fn pinned_game() -> EuchreGame {
+        let spec: DealSpec = DealSpec::new()
+            .hand(0, &cards(&[(Suit::Clubs, Rank::Nine), (Suit::Clubs, Rank::Ten), (Suit::Clubs, Rank::Queen),
+                              (Suit::Clubs, Rank::King), (Suit::Clubs, Rank::Ace)]))
+            .hand(1, &cards(&[(Suit::Hearts, Rank::Ace), (Suit::Hearts, Rank::King), (Suit::Clubs, Rank::Jack),
+                              (Suit::Spades, Rank::Nine), (Suit::Diamonds, Rank::Ten)]))
+            .hand(2, &cards(&[(Suit::Hearts, Rank::Nine), (Suit::Spades, Rank::Ten), (Suit::Spades, Rank::Queen),
+                              (Suit::Spades, Rank::King), (Suit::Diamonds, Rank::Ace)]))
+            .hand(3, &cards(&[(Suit::Hearts, Rank::Jack), (Suit::Spades, Rank::Ace), (Suit::Spades, Rank::Jack),
+                              (Suit::Diamonds, Rank::King), (Suit::Diamonds, Rank::Queen)]))
+            .flipped(Card::new(Suit::Diamonds, Rank::Nine));
+        EuchreGame::with_deal_spec(Some(0), Some(2), RuleSet::default(), &spec).unwrap()
+    }
+
+    #[test]
+    fn step_rejects_wrong_phase() {
+        let mut game: EuchreGame = pinned_game();
+        assert_eq!(game.step(Action::C9Discard).err(), Some(StepError::IllegalAction(Action::C9Discard)));
+        assert_eq!(game.step(Action::HAPlay).err(), Some(StepError::IllegalAction(Action::HAPlay)));
+        assert_eq!(game.step(Action::CallS).err(), Some(StepError::IllegalAction(Action::CallS)));
+
+        game.step(Action::Pick).unwrap();
+        assert_eq!(game.step(Action::Pass).err(), Some(StepError::IllegalAction(Action::Pass)));
+        assert_eq!(game.step(Action::C9Play).err(), Some(StepError::IllegalAction(Action::C9Play)));
+        assert_eq!(game.imm_player_ref(0).hand_clone().len(), 6);
+    }
+
+    #[test]
+    fn step_rejects_card_not_in_hand() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        assert_eq!(game.step(Action::HADiscard).err(), Some(StepError::CardNotInHand(Card::new(Suit::Hearts, Rank::Ace))));
+        game.step(Action::C9Discard).unwrap();
+
+        assert_eq!(game.step(Action::SAPlay).err(), Some(StepError::CardNotInHand(Card::new(Suit::Spades, Rank::Ace))));
+        assert_eq!(game.imm_player_ref(1).hand_clone().len(), 5);
+        assert!(game.get_state().center.is_none());
+    }
+
+    #[test]
+    fn step_rejects_not_following_suit() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        game.step(Action::C9Discard).unwrap();
+        game.step(Action::HAPlay).unwrap();
+
+        let err: StepError = game.step(Action::SKPlay).err().unwrap();
+        assert_eq!(err, StepError::MustFollowSuit { card: Card::new(Suit::Spades, Rank::King), led_suit: Suit::Hearts });
+        assert_eq!(err.to_string(), "Card SK does not follow the led suit H");
+        game.step(Action::H9Play).unwrap();
+
+        // the left bower is a diamond, so player 3 can't follow hearts
+        assert_eq!(game.get_legal_actions().len(), 5);
+    }
+
+    #[test]
+    fn left_bower_follows_trump() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        game.step(Action::C9Discard).unwrap();
+        game.step(Action::DTPlay).unwrap();
+        game.step(Action::DAPlay).unwrap();
+
+        assert_eq!(game.get_legal_actions(), vec![Action::HJPlay, Action::DKPlay, Action::DQPlay]);
+    }
+
+    #[test]
+    fn step_rejects_after_game_over() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
+        while !game.is_over() {
+            let action = game.get_legal_actions()[0];
+            game.step(action).unwrap();
+        }
+        assert_eq!(game.step(Action::Pass).err(), Some(StepError::GameOver));
+    }
+
+    /// Everything about a game that undo has to restore.
+    fn full_state(game: &EuchreGame) -> String {
+        let hands: Vec<(Vec<Card>, u8)> = game.players.iter().map(|x| (x.hand_clone(), x.get_tricks())).collect();
+        format!("{:?} {:?} {:?} {:?}", game.is_over, game.scores, hands, game.get_state())
+    }
+
+    #[test]
+    fn undo_every_step() {
+        for seed in 0..50 {
+            let rules: RuleSet = RuleSet { stick_the_dealer: seed % 2 == 0, ..RuleSet::default() };
+            let mut game: EuchreGame = EuchreGame::new(None, Some(seed), rules);
+            let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut states: Vec<String> = vec![full_state(&game)];
+
+            while !game.is_over() {
+                let action: Action = *game.get_legal_actions().choose(&mut gen).unwrap();
+                game.step(action).unwrap();
+                states.push(full_state(&game));
+            }
+
+            let taken: Vec<(u8, Action)> = game.get_action_history();
+            for i in (0..taken.len()).rev() {
+                assert_eq!(game.undo(), Some(taken[i]));
+                assert_eq!(full_state(&game), states[i]);
+            }
+            assert_eq!(game.undo(), None);
+        }
+    }
+
+    #[test]
+    fn undo_then_replay() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        game.step(Action::C9Discard).unwrap();
+        for action in [Action::HAPlay, Action::H9Play, Action::HJPlay, Action::CAPlay] {
+            game.step(action).unwrap();
+        }
+        assert_eq!(game.imm_player_ref(3).get_tricks(), 1);
+        assert_eq!(game.get_curr_player_id(), 3);
+
+        // take back the end of the trick and the dealer's pick up
+        game.undo_to(1).unwrap();
+        assert_eq!(game.imm_player_ref(3).get_tricks(), 0);
+        assert_eq!(game.imm_player_ref(0).hand_clone().len(), 6);
+        game.undo();
+        assert_eq!(game.imm_player_ref(0).hand_clone().len(), 5);
+        assert_eq!(game.get_legal_actions(), vec![Action::Pick, Action::Pass, Action::PickAlone]);
+
+        game.step(Action::Pass).unwrap();
+        assert_eq!(game.get_curr_player_id(), 2);
+    }
+
+    #[test]
+    fn undo_to_out_of_range() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pass).unwrap();
+        assert_eq!(game.undo_to(2), Err(EuchreError::UndoOutOfRange { index: 2, actions_taken: 1 }));
+        assert_eq!(game.get_action_history(), vec![(1, Action::Pass)]);
+    }
+
+    #[test]
+    fn tricks_are_recorded() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        game.step(Action::C9Discard).unwrap();
+        assert!(game.get_tricks().is_empty());
+
+        for action in [Action::HAPlay, Action::H9Play, Action::HJPlay] {
+            game.step(action).unwrap();
+            assert_eq!(game.get_last_trick(), None);
+        }
+        game.step(Action::CAPlay).unwrap();
+
+        // The left bower wins for player 3
+        let played: Vec<Card> = cards(&[(Suit::Hearts, Rank::Ace), (Suit::Hearts, Rank::Nine),
+                                         (Suit::Hearts, Rank::Jack), (Suit::Clubs, Rank::Ace)]);
+        let trick: Trick = Trick { winner: 3, order: vec![1, 2, 3, 0], cards: played };
+        assert_eq!(game.get_last_trick(), Some(trick.clone()));
+        assert_eq!(game.get_tricks(), vec![trick]);
+
+        game.undo();
+        assert_eq!(game.get_last_trick(), None);
+        assert!(game.get_tricks().is_empty());
+    }
+
+    #[test]
+    fn clone_continues_identically() {
+        let mut game: EuchreGame = EuchreGame::new(None, Some(6), RuleSet::default());
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(6);
+        for _ in 0..10 {
+            let action: Action = *game.get_legal_actions().choose(&mut gen).unwrap();
+            game.step(action).unwrap();
+        }
+
+        let mut branch: EuchreGame = game.clone();
+        while !game.is_over() {
+            let action: Action = *game.get_legal_actions().choose(&mut gen).unwrap();
+            game.step(action).unwrap();
+            branch.step(action).unwrap();
+            assert_eq!(full_state(&game), full_state(&branch));
+        }
+        assert_eq!(game._rng_gen, branch._rng_gen);
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        for seed in 0..20 {
+            let rules: RuleSet = RuleSet { stick_the_dealer: false, march_points: 3, ..RuleSet::default() };
+            let mut game: EuchreGame = EuchreGame::new(None, Some(seed), rules);
+            let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(seed);
+            for _ in 0..(seed % 25) {
+                if game.is_over() {
+                    break
+                }
+                let action: Action = *game.get_legal_actions().choose(&mut gen).unwrap();
+                game.step(action).unwrap();
+            }
+
+            let text: String = game.snapshot().to_string();
+            let snapshot: GameSnapshot = text.parse().unwrap();
+            assert_eq!(snapshot, game.snapshot());
+
+            let mut restored: EuchreGame = EuchreGame::restore(&snapshot).unwrap();
+            assert_eq!(full_state(&restored), full_state(&game));
+            assert_eq!(restored._rng_gen, game._rng_gen);
+            while !game.is_over() {
+                let action: Action = *game.get_legal_actions().choose(&mut gen).unwrap();
+                game.step(action).unwrap();
+                restored.step(action).unwrap();
+                assert_eq!(full_state(&restored), full_state(&game));
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_text_format() {
+        let mut game: EuchreGame = pinned_game();
+        game.step(Action::Pick).unwrap();
+        let text: String = game.snapshot().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "euchre-snapshot 1");
+        assert_eq!(lines[1], "rules true true 10 1 2 2 true 4");
+        assert_eq!(lines[2], "dealer 0");
+        assert_eq!(lines[3], "hand0 C9 CT CQ CK CA");
+        assert_eq!(lines[7], "flipped D9");
+        assert_eq!(lines[9], "moves 1:Pick");
+        assert!(lines[10].starts_with("rng "));
+    }
+
+    #[test]
+    fn restore_rejects_bad_snapshots() {
+        let game: EuchreGame = pinned_game();
+        let text: String = game.snapshot().to_string();
+        assert!(text.replace("moves ", "moves 2:Pass").parse::<GameSnapshot>().is_ok_and(|x| EuchreGame::restore(&x).is_err()));
+        assert!(text.replace("hand0 C9", "hand0 D9").parse::<GameSnapshot>().is_ok_and(|x| EuchreGame::restore(&x).is_err()));
+        assert!(text.replace("dealer 0", "dealer x").parse::<GameSnapshot>().is_err());
+        assert!(text.replace("euchre-snapshot 1", "euchre-snapshot 2").parse::<GameSnapshot>().is_err());
+        assert!(text.replace("flipped D9", "flipped Z9").parse::<GameSnapshot>().is_err());
+
+        // A seed of 64 bytes with a two byte character straddling the first pair of digits
+        let seed: usize = text.find("rng ").unwrap() + 5;
+        let multibyte: String = format!("{}é{}", &text[..seed], &text[seed + 2..]);
+        assert!(matches!(multibyte.parse::<GameSnapshot>(), Err(EuchreError::Parse(_))));
+    }
+
     #[test]
     fn get_dealer_valid_dealer() {
         let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(10);
-        assert_eq!(2, determine_dealer(Some(2), &mut gen));
-        assert!(determine_dealer(None, &mut gen) < 4);
+        assert_eq!(2, determine_dealer(Some(2), &mut gen).unwrap());
+        assert!(determine_dealer(None, &mut gen).unwrap() < 4);
     }
 
     #[test]
-    #[should_panic]
-    fn get_dealer_panic_on_invalid_dealer() {
+    fn get_dealer_err_on_invalid_dealer() {
         let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(10);
-        determine_dealer(Some(4), &mut gen);
+        assert_eq!(determine_dealer(Some(4), &mut gen), Err(EuchreError::InvalidDealer(4)));
+    }
+
+    #[test]
+    fn try_new_invalid_dealer() {
+        assert_eq!(EuchreGame::try_new(Some(7), None, RuleSet::default()).err(), Some(EuchreError::InvalidDealer(7)));
+        assert!(EuchreGame::try_new(Some(3), None, RuleSet::default()).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_panics_on_invalid_dealer() {
+        EuchreGame::new(Some(4), None, RuleSet::default());
     }
 
     #[test]
     fn random_playthrough() {
-        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10));
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
 
         while !game.is_over() {
             let action = game.get_legal_actions()[0];
-            let (_state, _player_id) = game.step(action);
+            let (_state, _player_id) = game.step(action).unwrap();
         }
         assert!(game.get_rewards().unwrap().len() == 4);
     }
@@ -390,7 +1037,7 @@ mod tests {
     #[test]
     fn rdm_100_games() {
         for i in 0..100 {
-            let mut game: EuchreGame = EuchreGame::new(Some(0), Some(i));
+            let mut game: EuchreGame = EuchreGame::new(Some(0), Some(i), RuleSet::default());
 
             while !game.is_over() {
                 let options: Vec<Action> = game.get_legal_actions();
@@ -398,9 +1045,94 @@ mod tests {
                     Some(i) => i,
                     None => {println!("{:?}", game.get_state()); return} ,
                 };
-                let (_state, _player_id) = game.step(action);
+                let (_state, _player_id) = game.step(action).unwrap();
             }
             assert!(game.get_rewards().unwrap().len() == 4);
         }
     }
+
+    #[test]
+    fn stick_the_dealer() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
+        for _ in 0..7 {
+            game.step(Action::Pass).unwrap();
+        }
+        assert_eq!(game.get_curr_player_id(), 0);
+        assert!(!game.get_legal_actions().contains(&Action::Pass));
+    }
+
+    #[test]
+    fn no_stick_the_dealer_all_pass_is_misdeal() {
+        let rules: RuleSet = RuleSet { stick_the_dealer: false, ..RuleSet::default() };
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), rules);
+        for _ in 0..7 {
+            game.step(Action::Pass).unwrap();
+        }
+        assert!(game.get_legal_actions().contains(&Action::Pass));
+        assert!(!game.is_over());
+
+        game.step(Action::Pass).unwrap();
+        assert!(game.is_over());
+        assert!(game.is_misdeal());
+        assert_eq!(game.get_rewards().unwrap(), vec![0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn alone_not_allowed() {
+        let rules: RuleSet = RuleSet { allow_alone: false, ..RuleSet::default() };
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), rules);
+        assert!(!game.get_legal_actions().iter().any(|x| x.is_alone()));
+        for _ in 0..4 {
+            game.step(Action::Pass).unwrap();
+        }
+        assert!(!game.get_legal_actions().iter().any(|x| x.is_alone()));
+    }
+
+    #[test]
+    fn pick_alone_skips_partner() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
+        game.step(Action::PickAlone).unwrap();
+        assert_eq!(game.get_state().alone_actor, Some(1));
+
+        // dealer still picks up and discards
+        assert_eq!(game.get_curr_player_id(), 0);
+        let discard: Action = game.get_legal_actions()[0];
+        game.step(discard).unwrap();
+        assert_eq!(game.get_curr_player_id(), 1);
+        assert_eq!(game.get_state().order, vec![1, 2, 0]);
+
+        while !game.is_over() {
+            assert_ne!(game.get_curr_player_id(), 3);
+            let action = game.get_legal_actions()[0];
+            game.step(action).unwrap();
+        }
+        assert_eq!(game.imm_player_ref(3).hand_clone().len(), 5);
+        assert_eq!(game.get_player_tricks().iter().sum::<u8>(), 5);
+    }
+
+    #[test]
+    fn dealers_partner_alone_dealer_sits_out() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
+        game.step(Action::Pass).unwrap();
+        game.step(Action::PickAlone).unwrap();
+
+        // dealer sits out so nobody picks up the flipped card
+        assert_eq!(game.get_curr_player_id(), 1);
+        assert_eq!(game.imm_player_ref(0).hand_clone().len(), 5);
+        assert_eq!(game.get_state().order, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn call_alone_left_of_dealer_sits_out() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(10), RuleSet::default());
+        for _ in 0..6 {
+            game.step(Action::Pass).unwrap();
+        }
+        let call: Action = *game.get_legal_actions().iter().find(|x| x.is_alone()).unwrap();
+        game.step(call).unwrap();
+
+        // player 3 is alone, so player 1 sits out and player 2 leads
+        assert_eq!(game.get_curr_player_id(), 2);
+        assert_eq!(game.get_state().order, vec![2, 3, 0]);
+    }
 }
@@ -1,8 +1,10 @@
 use crate::card::{Card, Suit};
 use crate::action::FlippedChoice;
 use crate::action::Action;
+use crate::rules::RuleSet;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A struct which represents the game state from the perspective of a certain player
 pub struct ScopedGameState {
     pub dealer_actor: u8,
@@ -12,6 +14,8 @@ pub struct ScopedGameState {
 
     /// None if no choice on trump has been made
     pub calling_actor: Option<u8>,
+    /// None if nobody is going alone; otherwise the same as `calling_actor`
+    pub alone_actor: Option<u8>,
     /// None if no choice on trump has been made... Flipped card still facing up
     pub flipped_choice: Option<FlippedChoice>,
 
@@ -29,8 +33,13 @@ pub struct ScopedGameState {
     pub center: Option<Vec<Card>>,
     /// All empty if not in 2nd round; cards are only in center during 1st.
     pub previous_played: Vec<Vec<Card>>,
+    /// The card the current player threw away after picking up, so only ever set for the dealer
+    pub discarded: Option<Card>,
 
     /// Guranteed to always exist, only empty after last turn of game.
     pub legal_actions: Vec<Action>,
 
+    /// House rules the hand is played and scored by
+    pub rules: RuleSet,
+
 }
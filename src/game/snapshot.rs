@@ -0,0 +1,159 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+
+use crate::card::Card;
+use crate::action::Action;
+use crate::dealer::Deal;
+use crate::error::EuchreError;
+use crate::rules::RuleSet;
+
+/// First line of every snapshot, bumped whenever the format changes.
+const SNAPSHOT_HEADER: &str = "euchre-snapshot 1";
+
+/// The exact position of a `ChaCha8Rng`, so it can be rebuilt to produce the same numbers.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RngState {
+    pub seed: [u8; 32],
+    pub stream: u64,
+    pub word_pos: u128,
+}
+
+impl RngState {
+    /// Record the position of `gen`.
+    pub fn of(gen: &ChaCha8Rng) -> RngState {
+        RngState {
+            seed: gen.get_seed(),
+            stream: gen.get_stream(),
+            word_pos: gen.get_word_pos(),
+        }
+    }
+
+    /// Rebuild a generator at this position.
+    pub fn to_rng(&self) -> ChaCha8Rng {
+        let mut gen: ChaCha8Rng = ChaCha8Rng::from_seed(self.seed);
+        gen.set_stream(self.stream);
+        gen.set_word_pos(self.word_pos);
+        gen
+    }
+}
+
+/// Everything needed to rebuild an `EuchreGame` exactly as it was:
+/// the rules, the deal, every action taken since, and the position of the game's random generator.
+///
+/// A snapshot can be written as text with `to_string` and read back with `parse`.
+/// The text holds one labelled line per field, with cards and actions
+/// written the same way they are displayed, e.g. `HJ` and `2:Pick`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameSnapshot {
+    pub rules: RuleSet,
+    pub dealer_id: u8,
+    pub deal: Deal,
+    /// The id of the player who took each action, and the action
+    pub moves: Vec<(u8, Action)>,
+    pub rng: RngState,
+}
+
+impl fmt::Display for GameSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cards = |cards: &[Card]| cards.iter().map(|x| x.to_string()).collect::<Vec<String>>().join(" ");
+        let rules: &RuleSet = &self.rules;
+
+        writeln!(f, "{}", SNAPSHOT_HEADER)?;
+        writeln!(f, "rules {} {} {} {} {} {} {} {}",
+                 rules.stick_the_dealer, rules.redeal_after_misdeal, rules.points_to_win, rules.make_points,
+                 rules.march_points, rules.euchre_points, rules.allow_alone, rules.alone_march_points)?;
+        writeln!(f, "dealer {}", self.dealer_id)?;
+        for (i, hand) in self.deal.hands.iter().enumerate() {
+            writeln!(f, "hand{} {}", i, cards(hand))?;
+        }
+        writeln!(f, "flipped {}", self.deal.flipped_card)?;
+        writeln!(f, "buried {}", cards(&self.deal.buried))?;
+        let moves: Vec<String> = self.moves.iter().map(|(id, action)| format!("{}:{}", id, action)).collect();
+        writeln!(f, "moves {}", moves.join(" "))?;
+        let seed: String = self.rng.seed.iter().map(|x| format!("{:02x}", x)).collect();
+        write!(f, "rng {} {} {}", seed, self.rng.stream, self.rng.word_pos)
+    }
+}
+
+impl FromStr for GameSnapshot {
+    type Err = EuchreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |msg: &str| EuchreError::Parse(format!("Invalid snapshot: {}", msg));
+        let mut lines = s.lines();
+        if lines.next() != Some(SNAPSHOT_HEADER) {
+            return Err(err("missing header"));
+        }
+        // Returns the words of the next line, after checking its label
+        let mut field = |label: &str| -> Result<Vec<&str>, EuchreError> {
+            let line: &str = lines.next().ok_or_else(|| err(&format!("missing {}", label)))?;
+            let mut words: Vec<&str> = line.split_whitespace().collect();
+            if words.first() != Some(&label) {
+                return Err(err(&format!("expected {} but found {:?}", label, line)));
+            }
+            words.remove(0);
+            Ok(words)
+        };
+        let number = |word: &str| word.parse::<u8>().map_err(|_| err(&format!("{:?} is not a number", word)));
+        let boolean = |word: &str| word.parse::<bool>().map_err(|_| err(&format!("{:?} is not true or false", word)));
+        let cards = |words: &[&str]| words.iter().map(|x| Card::from_str(x)).collect::<Result<Vec<Card>, EuchreError>>();
+
+        let words: Vec<&str> = field("rules")?;
+        if words.len() != 8 {
+            return Err(err("rules needs 8 values"));
+        }
+        let rules: RuleSet = RuleSet {
+            stick_the_dealer: boolean(words[0])?,
+            redeal_after_misdeal: boolean(words[1])?,
+            points_to_win: number(words[2])?,
+            make_points: number(words[3])?,
+            march_points: number(words[4])?,
+            euchre_points: number(words[5])?,
+            allow_alone: boolean(words[6])?,
+            alone_march_points: number(words[7])?,
+        };
+
+        let dealer_id: u8 = number(field("dealer")?.first().ok_or_else(|| err("missing dealer id"))?)?;
+        let mut hands: Vec<Vec<Card>> = vec![];
+        for i in 0..4 {
+            hands.push(cards(&field(&format!("hand{}", i))?)?);
+        }
+        let flipped: Vec<Card> = cards(&field("flipped")?)?;
+        let flipped_card: Card = *flipped.first().ok_or_else(|| err("missing flipped card"))?;
+        let buried: Vec<Card> = cards(&field("buried")?)?;
+
+        let mut moves: Vec<(u8, Action)> = vec![];
+        for word in field("moves")? {
+            let (id, action) = word.split_once(':').ok_or_else(|| err(&format!("{:?} is not a move", word)))?;
+            let action: Action = Action::from_str(action).map_err(|_| err(&format!("{:?} is not an action", action)))?;
+            moves.push((number(id)?, action));
+        }
+
+        let words: Vec<&str> = field("rng")?;
+        if words.len() != 3 || words[0].len() != 64 || !words[0].is_ascii() {
+            return Err(err("rng needs a 64 digit hex seed, a stream and a word position"));
+        }
+        let mut seed: [u8; 32] = [0; 32];
+        for (i, byte) in seed.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&words[0][i * 2..i * 2 + 2], 16).map_err(|_| err("seed is not hex"))?;
+        }
+        let rng: RngState = RngState {
+            seed,
+            stream: words[1].parse().map_err(|_| err("stream is not a number"))?,
+            word_pos: words[2].parse().map_err(|_| err("word position is not a number"))?,
+        };
+
+        Ok(GameSnapshot {
+            rules,
+            dealer_id,
+            deal: Deal { hands, flipped_card, buried },
+            moves,
+            rng,
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use std::fmt;
+
+use crate::card::{Card, Suit};
+use crate::action::Action;
+
+/// The reason `EuchreGame::step` rejected an action.
+/// The game state is left untouched whenever one of these is returned.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepError {
+    /// The hand has already been scored
+    GameOver,
+    /// The action can't be taken in the current phase of the game,
+    /// e.g. a discard while bidding, or calling the suit that was turned down
+    IllegalAction(Action),
+    /// The current player tried to play or discard a card they don't hold
+    CardNotInHand(Card),
+    /// The current player can follow the led suit but tried to play another suit
+    MustFollowSuit {
+        card: Card,
+        led_suit: Suit,
+    },
+}
+
+impl fmt::Display for StepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepError::GameOver => write!(f, "The game is already over"),
+            StepError::IllegalAction(action) => write!(f, "{} is not legal at this point of the game", action),
+            StepError::CardNotInHand(card) => write!(f, "Card {} is not in the current player's hand", card),
+            StepError::MustFollowSuit { card, led_suit } => write!(f, "Card {} does not follow the led suit {}", card, led_suit),
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
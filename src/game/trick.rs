@@ -0,0 +1,13 @@
+use crate::card::Card;
+
+/// A completed trick: who played each card, and who won it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trick {
+    /// Id of the player who won the trick, and leads the next one
+    pub winner: u8,
+    /// The players in the order they played, starting with the leader
+    pub order: Vec<u8>,
+    /// `cards[i]` was played by `order[i]`
+    pub cards: Vec<Card>,
+}
@@ -1,23 +1,52 @@
 
 use crate::card::{Card, Suit};
+use crate::rules::RuleSet;
 
+/// How a hand ended for the team that called trump.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandOutcome {
+    /// The calling team took 3 or 4 tricks
+    Made,
+    /// The calling team took all 5 tricks
+    March,
+    /// A player going alone took all 5 tricks
+    AloneMarch,
+    /// The calling team took fewer than 3 tricks
+    Euchred,
+}
+
+#[derive(Clone)]
 pub struct Judger {
+    rules: RuleSet,
+}
 
+impl Default for Judger {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Judger {
+    /// Creates a `Judger` that scores with the default `RuleSet`.
     pub fn new() -> Judger {
-        Judger {}
+        Self::with_rules(RuleSet::default())
+    }
+
+    /// Creates a `Judger` that scores using the points in `rules`.
+    pub fn with_rules(rules: RuleSet) -> Judger {
+        Judger { rules }
     }
 
     /// return the id of the player that won this trick.  
     /// Assumes that the indices of center corresponds to the indices of items in `order`,
     /// and that the first index represents who led this trick.
-    pub fn judge_trick(&self, trump: Suit, center: &Vec<Card>, order: &Vec<u8>) -> u8{
+    /// A trick has 3 cards when a player is going alone, otherwise 4.
+    pub fn judge_trick(&self, trump: Suit, center: &[Card], order: &[u8]) -> u8{
         let mut candidate_player: u8 = order[0];
         let mut candidate_card: Card = center[0];
 
-        for i in 1..=3usize {
+        for i in 1..center.len() {
             if candidate_card.is_lower(trump, center[i]) {
                 candidate_card = center[i];
                 candidate_player = order[i];
@@ -26,28 +55,148 @@ impl Judger {
         candidate_player
     }
 
-    /// Judge which team won the current round of euchre.
+    /// Decide how the hand went for the team that called trump,
+    /// given how many tricks that team took and whether the caller went alone.
+    pub fn hand_outcome(caller_tricks: u8, alone: bool) -> HandOutcome {
+        match (caller_tricks, alone) {
+            (5, true) => HandOutcome::AloneMarch,
+            (5, false) => HandOutcome::March,
+            (3..=4, _) => HandOutcome::Made,
+            _ => HandOutcome::Euchred,
+        }
+    }
+
+    /// Judge which team won the current round of euchre, and return the points awarded to each player.  
+    /// Both players of a team are awarded the team's points.
     /// Assumes that the sum of all elements in `tricks` is 5 (one for each trick)
-    pub fn judge_round(&self, tricks: Vec<u8>, calling_id: u8) -> Vec<u8> {
-        let team_1_tricks: u8 = tricks[0] + tricks[2];
-        let team_1_called: bool = if calling_id % 2 == 0 {true} else {false};
-
-        if team_1_tricks == 5 { //team 1 won all the tricks
-            vec![2,0,2,0]
-        } else if team_1_tricks >= 3 {
-            if team_1_called { // team 1 called & won
-                vec![1,0,1,0]
-            } else { // team 1 euchered team 2
-                vec![2,0,1,0]
+    pub fn judge_round(&self, tricks: Vec<u8>, calling_id: u8, alone: bool) -> Vec<u8> {
+        let calling_team: u8 = calling_id % 2;
+        let defending_team: u8 = 1 - calling_team;
+        let caller_tricks: u8 = tricks[usize::from(calling_team)] + tricks[usize::from(calling_team + 2)];
+
+        match Self::hand_outcome(caller_tricks, alone) {
+            HandOutcome::Made => Self::team_points(calling_team, self.rules.make_points),
+            HandOutcome::March => Self::team_points(calling_team, self.rules.march_points),
+            HandOutcome::AloneMarch => Self::team_points(calling_team, self.rules.alone_march_points),
+            HandOutcome::Euchred => Self::team_points(defending_team, self.rules.euchre_points),
+        }
+    }
+
+    /// Award `points` to both players of team 0 (players 0 & 2) or team 1 (players 1 & 3).
+    fn team_points(team: u8, points: u8) -> Vec<u8> {
+        if team == 0 {vec![points, 0, points, 0]} else {vec![0, points, 0, points]}
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Rank;
+
+    #[test]
+    fn judge_three_card_trick() {
+        let judger: Judger = Judger::new();
+        let center: Vec<Card> = vec![Card::new(Suit::Hearts, Rank::Ace),
+                                     Card::new(Suit::Spades, Rank::Nine),
+                                     Card::new(Suit::Hearts, Rank::King)];
+        assert_eq!(judger.judge_trick(Suit::Spades, &center, &[1, 2, 0]), 2);
+        assert_eq!(judger.judge_trick(Suit::Clubs, &center, &[1, 2, 0]), 1);
+    }
+
+    #[test]
+    fn loner_march_scores_four() {
+        let judger: Judger = Judger::new();
+        assert_eq!(judger.judge_round(vec![5,0,0,0], 0, true), vec![4,0,4,0]);
+        assert_eq!(judger.judge_round(vec![0,0,0,5], 3, true), vec![0,4,0,4]);
+        assert_eq!(judger.judge_round(vec![5,0,0,0], 0, false), vec![2,0,2,0]);
+    }
+
+    #[test]
+    fn points_come_from_rules() {
+        let rules: RuleSet = RuleSet {
+            make_points: 3,
+            march_points: 5,
+            euchre_points: 7,
+            alone_march_points: 9,
+            ..RuleSet::default()
+        };
+        let judger: Judger = Judger::with_rules(rules);
+        assert_eq!(judger.judge_round(vec![2,0,1,2], 0, false), vec![3,0,3,0]);
+        assert_eq!(judger.judge_round(vec![3,0,2,0], 2, false), vec![5,0,5,0]);
+        assert_eq!(judger.judge_round(vec![1,2,0,2], 0, false), vec![0,7,0,7]);
+        assert_eq!(judger.judge_round(vec![3,0,2,0], 2, true), vec![9,0,9,0]);
+    }
+
+    /// Every way 5 tricks can be split between 4 players.
+    fn trick_splits() -> Vec<Vec<u8>> {
+        let mut splits: Vec<Vec<u8>> = vec![];
+        for p0 in 0..=5u8 {
+            for p1 in 0..=(5 - p0) {
+                for p2 in 0..=(5 - p0 - p1) {
+                    splits.push(vec![p0, p1, p2, 5 - p0 - p1 - p2]);
+                }
             }
-        } else if team_1_tricks < 2 {
-            if team_1_called { // team 2 euchered team 1
-                vec![0,2,0,2]
-            } else { // team 2 called & won
-                vec![0,1,0,1]
+        }
+        splits
+    }
+
+    #[test]
+    fn judge_round_every_caller_and_split() {
+        // (tricks taken by the calling team, alone, calling team points, defending team points)
+        let table: [(u8, bool, u8, u8); 12] = [
+            (0, false, 0, 2), (1, false, 0, 2), (2, false, 0, 2),
+            (3, false, 1, 0), (4, false, 1, 0), (5, false, 2, 0),
+            (0, true, 0, 2), (1, true, 0, 2), (2, true, 0, 2),
+            (3, true, 1, 0), (4, true, 1, 0), (5, true, 4, 0),
+        ];
+        let judger: Judger = Judger::new();
+
+        let splits: Vec<Vec<u8>> = trick_splits();
+        assert_eq!(splits.len(), 56);
+        for calling_id in 0..4u8 {
+            for tricks in &splits {
+                for &(caller_tricks, alone, caller_points, defender_points) in &table {
+                    let team_tricks: u8 = tricks[usize::from(calling_id % 2)] + tricks[usize::from(calling_id % 2 + 2)];
+                    if team_tricks != caller_tricks {
+                        continue;
+                    }
+                    let scores: Vec<u8> = judger.judge_round(tricks.clone(), calling_id, alone);
+                    for player in 0..4u8 {
+                        let expected: u8 = if player % 2 == calling_id % 2 {caller_points} else {defender_points};
+                        assert_eq!(scores[usize::from(player)], expected,
+                                   "caller {} tricks {:?} alone {}", calling_id, tricks, alone);
+                    }
+                }
             }
-        } else { // team 2 won all the tricks
-            vec![0,2,0,2]
         }
     }
+
+    #[test]
+    fn regression_two_tricks_and_defender_march() {
+        let judger: Judger = Judger::new();
+        // team 1 called and took two tricks
+        assert_eq!(judger.judge_round(vec![1,2,1,1], 0, false), vec![0,2,0,2]);
+        // team 2 called and took two tricks
+        assert_eq!(judger.judge_round(vec![2,1,1,1], 1, false), vec![2,0,2,0]);
+        // team 2 called and took all five
+        assert_eq!(judger.judge_round(vec![0,3,0,2], 3, false), vec![0,2,0,2]);
+        // team 1 euchred team 2
+        assert_eq!(judger.judge_round(vec![2,1,1,1], 3, false), vec![2,0,2,0]);
+    }
+
+    #[test]
+    fn hand_outcomes() {
+        assert_eq!(Judger::hand_outcome(0, false), HandOutcome::Euchred);
+        assert_eq!(Judger::hand_outcome(2, true), HandOutcome::Euchred);
+        assert_eq!(Judger::hand_outcome(3, true), HandOutcome::Made);
+        assert_eq!(Judger::hand_outcome(5, false), HandOutcome::March);
+        assert_eq!(Judger::hand_outcome(5, true), HandOutcome::AloneMarch);
+    }
+
+    #[test]
+    fn loner_making_bid_scores_one() {
+        let judger: Judger = Judger::new();
+        assert_eq!(judger.judge_round(vec![4,1,0,0], 0, true), vec![1,0,1,0]);
+    }
 }
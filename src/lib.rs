@@ -5,6 +5,11 @@ pub mod player;
 pub mod dealer;
 pub mod action;
 pub mod judger;
+pub mod rules;
 pub mod env;
 pub mod agent;
+pub mod error;
+pub mod solver;
 mod utils;
+#[cfg(feature = "serde")]
+mod serde_str;
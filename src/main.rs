@@ -7,6 +7,7 @@ fn main() {
     time_to_play_x_games(1_000_000);
 }   
 
+#[allow(dead_code)]
 fn play_one_game() {
     let agents:Vec<Box<dyn Agent>> = vec![Box::new(HumanAgent::default()),
     Box::new(RandomAgent::new(Some(42))),
@@ -33,7 +34,7 @@ fn time_to_play_x_games(x: u64) -> bool {
                                           Box::new(RandomAgent::new(None)),
                                           Box::new(RandomAgent::new(None)),];
 
-    let config: GameConfig = GameConfig::new(agents, None, None, false);
+    let config: GameConfig = GameConfig::new(agents, None, None, false).without_log();
     let mut env: EuchreEnv = EuchreEnv::new(config);
 
     // Run
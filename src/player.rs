@@ -1,5 +1,6 @@
 use crate::card::Card;
 
+#[derive(Clone)]
 pub struct Player {
     player_id: u8,
     tricks: u8,
@@ -23,6 +24,11 @@ impl Player {
         self.tricks += 1;
     }
 
+    /// Take back a trick given by `award_trick`.
+    pub fn revoke_trick(&mut self) {
+        self.tricks -= 1;
+    }
+
     pub fn get_tricks(&self) -> u8 {
         self.tricks
     }
@@ -62,6 +68,15 @@ mod tests {
         assert_eq!(1, play.tricks);
     }
 
+    #[test]
+    fn revoke_trick() {
+        let mut play: Player = Player::new(2);
+        play.award_trick();
+        play.award_trick();
+        play.revoke_trick();
+        assert_eq!(1, play.tricks);
+    }
+
     #[test]
     fn get_tricks_won() {
         let mut play: Player = Player::new(3);
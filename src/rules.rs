@@ -0,0 +1,99 @@
+/// House rules that change how a hand or match of euchre is played and scored.
+///
+/// The `Default` rule set is standard euchre with stick-the-dealer.
+/// Individual rules can be changed with struct update syntax:
+///
+/// ```
+/// use euchre_rs::rules::RuleSet;
+///
+/// let rules = RuleSet { stick_the_dealer: false, ..RuleSet::default() };
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RuleSet {
+    /// The dealer may not pass in the second round of bidding
+    pub stick_the_dealer: bool,
+    /// Only used by `EuchreMatch` to pick the next dealer. Without stick-the-dealer a hand
+    /// where all four players pass twice ends as a misdeal scoring no points, after which
+    /// the same dealer deals again when `true`, otherwise the deal passes left as usual.
+    pub redeal_after_misdeal: bool,
+    /// Points a team needs to win a match
+    pub points_to_win: u8,
+    /// Points for the calling team taking 3 or 4 tricks
+    pub make_points: u8,
+    /// Points for the calling team taking all 5 tricks
+    pub march_points: u8,
+    /// Points for the defending team when the calling team takes fewer than 3 tricks
+    pub euchre_points: u8,
+    /// Players may order up or call trump alone
+    pub allow_alone: bool,
+    /// Points for a player going alone who takes all 5 tricks
+    pub alone_march_points: u8,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            stick_the_dealer: true,
+            redeal_after_misdeal: true,
+            points_to_win: 10,
+            make_points: 1,
+            march_points: 2,
+            euchre_points: 2,
+            allow_alone: true,
+            alone_march_points: 4,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::game::EuchreGame;
+    use crate::judger::Judger;
+
+    #[test]
+    fn every_rule_combination() {
+        for stick_the_dealer in [false, true] {
+            for redeal_after_misdeal in [false, true] {
+                for allow_alone in [false, true] {
+                    let rules: RuleSet = RuleSet {
+                        stick_the_dealer,
+                        redeal_after_misdeal,
+                        allow_alone,
+                        make_points: 1,
+                        march_points: 3,
+                        euchre_points: 5,
+                        alone_march_points: 6,
+                        ..RuleSet::default()
+                    };
+                    let case: String = format!("{:?}", rules);
+
+                    // Everyone passes twice: the dealer is stuck, or the hand is a misdeal whatever the redeal rule
+                    let mut game: EuchreGame = EuchreGame::new(Some(1), Some(3), rules);
+                    assert_eq!(game.get_legal_actions().iter().any(|x| x.is_alone()), allow_alone, "{}", case);
+                    for _ in 0..7 {
+                        game.step(Action::Pass).unwrap();
+                    }
+                    let dealer_actions: Vec<Action> = game.get_legal_actions();
+                    assert_eq!(dealer_actions.contains(&Action::Pass), !stick_the_dealer, "{}", case);
+                    assert_eq!(dealer_actions.iter().any(|x| x.is_alone()), allow_alone, "{}", case);
+                    if !stick_the_dealer {
+                        game.step(Action::Pass).unwrap();
+                        assert!(game.is_over() && game.is_misdeal(), "{}", case);
+                        assert_eq!(game.get_rewards().unwrap(), vec![0, 0, 0, 0], "{}", case);
+                    }
+
+                    // Hands are scored with the custom points
+                    let judger: Judger = Judger::with_rules(rules);
+                    assert_eq!(judger.judge_round(vec![3, 0, 2, 0], 0, false), vec![3, 0, 3, 0], "{}", case);
+                    assert_eq!(judger.judge_round(vec![3, 0, 2, 0], 0, true), vec![6, 0, 6, 0], "{}", case);
+                    assert_eq!(judger.judge_round(vec![2, 1, 1, 1], 0, false), vec![1, 0, 1, 0], "{}", case);
+                    assert_eq!(judger.judge_round(vec![0, 2, 0, 3], 0, false), vec![0, 5, 0, 5], "{}", case);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Serde support for types with a compact text form.
+//!
+//! Cards, suits, ranks and actions are written the same way they are displayed,
+//! e.g. `"HJ"` for the jack of hearts and `"HJPlay"` for playing it,
+//! and read back with their `FromStr` implementations.
+//! Every other public data type derives `Serialize` and `Deserialize` directly.
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+
+use crate::action::Action;
+use crate::card::{Card, Rank, Suit};
+
+macro_rules! serde_as_str {
+    ($($t:ty),*) => {
+        $(
+            impl Serialize for $t {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $t {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let text: String = String::deserialize(deserializer)?;
+                    <$t>::from_str(&text).map_err(|_| D::Error::custom(format!("{:?} is not a valid {}", text, stringify!($t))))
+                }
+            }
+        )*
+    };
+}
+
+serde_as_str!(Card, Suit, Rank, Action);
+
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+
+    use serde::de::DeserializeOwned;
+    use strum::IntoEnumIterator;
+
+    use crate::action::FlippedChoice;
+    use crate::dealer::{Deal, DealSlot, DealSpec};
+    use crate::env::config::GameSettings;
+    use crate::env::euchre_match::{HandRecord, MatchResult};
+    use crate::error::EuchreError;
+    use crate::game::EuchreGame;
+    use crate::game::scoped_state::ScopedGameState;
+    use crate::game::snapshot::GameSnapshot;
+    use crate::game::step_error::StepError;
+    use crate::game::trick::Trick;
+    use crate::judger::HandOutcome;
+    use crate::rules::RuleSet;
+    use super::*;
+
+    /// Serialize `value` to json, check it reads back as the same value, and return the json.
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> String {
+        let json: String = serde_json::to_string(value).unwrap();
+        let back: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(&back, value);
+        json
+    }
+
+    #[test]
+    fn compact_forms() {
+        assert_eq!(round_trip(&Card::new(Suit::Hearts, Rank::Jack)), "\"HJ\"");
+        assert_eq!(round_trip(&Suit::Clubs), "\"C\"");
+        assert_eq!(round_trip(&Rank::Ten), "\"T\"");
+        assert_eq!(round_trip(&Action::HJPlay), "\"HJPlay\"");
+        assert_eq!(round_trip(&Action::CallSAlone), "\"CallSAlone\"");
+    }
+
+    #[test]
+    fn every_card_and_action() {
+        for suit in Suit::iter() {
+            round_trip(&suit);
+            for rank in Rank::iter() {
+                round_trip(&rank);
+                round_trip(&Card::new(suit, rank));
+            }
+        }
+        for action in Action::iter() {
+            round_trip(&action);
+        }
+    }
+
+    #[test]
+    fn bad_strings_are_rejected() {
+        assert!(serde_json::from_str::<Card>("\"JH\"").is_err());
+        assert!(serde_json::from_str::<Card>("12").is_err());
+        assert!(serde_json::from_str::<Action>("\"Fold\"").is_err());
+        assert!(serde_json::from_str::<Suit>("\"X\"").is_err());
+    }
+
+    #[test]
+    fn game_types() {
+        round_trip(&FlippedChoice::PickedUp);
+        round_trip(&FlippedChoice::TurnedDown);
+        round_trip(&HandOutcome::AloneMarch);
+        round_trip(&RuleSet { stick_the_dealer: false, points_to_win: 11, ..RuleSet::default() });
+        round_trip(&GameSettings { dealer_id: Some(1), seed: Some(42), verbose: true, rules: RuleSet::default() });
+        round_trip(&GameSettings::default());
+        round_trip(&StepError::MustFollowSuit { card: Card::new(Suit::Spades, Rank::Nine), led_suit: Suit::Hearts });
+        round_trip(&EuchreError::IllegalMove { index: 3, player_id: 2, action: Action::Pass, reason: String::from("is illegal") });
+        round_trip(&EuchreError::Step(StepError::GameOver));
+        round_trip(&DealSlot::Hand(2));
+        round_trip(&DealSpec::new().hand(1, &[Card::new(Suit::Hearts, Rank::Ace)]).flipped(Card::new(Suit::Clubs, Rank::Jack)));
+
+        let record: HandRecord = HandRecord { dealer_id: 3, points: [0, 2], seed: Some(12) };
+        round_trip(&record);
+        round_trip(&MatchResult { winning_team: 1, final_score: [7, 10], history: vec![record] });
+    }
+
+    #[test]
+    fn states_and_snapshots() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(9), RuleSet::default());
+        let deal: Deal = game.get_deal().clone();
+        round_trip(&deal);
+
+        while !game.is_over() {
+            let state: ScopedGameState = game.get_state();
+            round_trip(&state);
+            game.step(state.legal_actions[0]).unwrap();
+        }
+        round_trip(&game.get_state());
+
+        for trick in game.get_tricks() {
+            round_trip::<Trick>(&trick);
+        }
+
+        let snapshot: GameSnapshot = game.snapshot();
+        round_trip(&snapshot);
+        round_trip(&snapshot.rng);
+    }
+}
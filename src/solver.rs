@@ -0,0 +1,443 @@
+//! Perfect-information ("double dummy") solver for the play of a hand.
+//!
+//! Given every player's cards, trump and who is on lead, the solver searches every way
+//! the remaining tricks can be played with alpha-beta minimax,
+//! where team 0 (players 0 & 2) maximizes its tricks and team 1 (players 1 & 3) minimizes them.
+
+use std::collections::HashMap;
+
+use crate::action::Action;
+use crate::card::{Card, Rank, Suit};
+use crate::error::EuchreError;
+use crate::game::EuchreGame;
+use crate::game::scoped_state::ScopedGameState;
+use crate::judger::Judger;
+
+/// A point in the play of a hand, with every card known.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Position {
+    /// The cards each player still holds, indexed by player id.
+    /// A player sitting out has no cards.
+    pub hands: Vec<Vec<Card>>,
+    pub trump: Suit,
+    /// The player who led, or leads, the current trick
+    pub leader: u8,
+    /// The partner of a player going alone
+    pub sitting_out: Option<u8>,
+    /// Cards already played to the current trick, starting with the leader's
+    pub center: Vec<Card>,
+}
+
+impl Position {
+    /// A position at the start of a trick led by `leader`, with nobody going alone.
+    pub fn new(hands: Vec<Vec<Card>>, trump: Suit, leader: u8) -> Position {
+        Position {
+            hands,
+            trump,
+            leader,
+            sitting_out: None,
+            center: vec![],
+        }
+    }
+
+    /// The position of a game whose trump has been decided and whose dealer has discarded.  
+    /// Returns an error if the game is still bidding or discarding, or is over.
+    pub fn from_game(game: &EuchreGame) -> Result<Position, EuchreError> {
+        let state: ScopedGameState = game.get_state();
+        let playing: bool = state.legal_actions.iter().any(|x| x.is_play());
+        let trump: Suit = state.trump.filter(|_| playing)
+            .ok_or_else(|| EuchreError::InvalidDeal(String::from("Only games in the play phase can be solved")))?;
+
+        let sitting_out: Option<u8> = state.alone_actor.map(|x| (x + 2) % 4);
+        let mut hands: Vec<Vec<Card>> = game.get_hands();
+        if let Some(id) = sitting_out {
+            hands[usize::from(id)].clear();
+        }
+
+        Ok(Position {
+            hands,
+            trump,
+            leader: state.order[0],
+            sitting_out,
+            center: state.center.unwrap_or_default(),
+        })
+    }
+
+    /// The players taking part in the hand, in the order they play a trick led by `leader`.
+    fn order_from(&self, leader: u8) -> Vec<u8> {
+        (0..4).map(|x| (leader + x) % 4).filter(|x| Some(*x) != self.sitting_out).collect()
+    }
+
+    /// Returns an error if the position can't be reached in a hand of euchre.
+    fn validate(&self) -> Result<(), EuchreError> {
+        let err = |msg: &str| Err(EuchreError::InvalidDeal(format!("Invalid position: {}", msg)));
+        if self.hands.len() != 4 {
+            return err("there must be 4 hands")
+        }
+        if self.trump == Suit::Unset {
+            return err("trump must be a suit")
+        }
+        if self.hands.iter().flatten().chain(&self.center).any(|x| x.suit() == Suit::Unset || x.rank() == Rank::Unset) {
+            return err("every card must have a suit and a rank")
+        }
+        if self.leader > 3 || Some(self.leader) == self.sitting_out || self.sitting_out.is_some_and(|x| x > 3) {
+            return err("the leader must be a player who isn't sitting out")
+        }
+        let order: Vec<u8> = self.order_from(self.leader);
+        if self.center.len() >= order.len() {
+            return err("the current trick is already complete")
+        }
+        if self.sitting_out.is_some_and(|x| !self.hands[usize::from(x)].is_empty()) {
+            return err("a player sitting out can't hold cards")
+        }
+        let remaining: usize = self.hands[usize::from(self.leader)].len() + usize::from(!self.center.is_empty());
+        if remaining == 0 || remaining > 5 {
+            return err("there must be between 1 and 5 tricks left")
+        }
+        for (i, id) in order.iter().enumerate() {
+            let played: usize = usize::from(i < self.center.len());
+            if self.hands[usize::from(*id)].len() + played != remaining {
+                return err("every player must have played the same number of cards")
+            }
+        }
+        let mut all: Vec<Card> = self.hands.concat();
+        all.extend_from_slice(&self.center);
+        for (i, card) in all.iter().enumerate() {
+            if all[i + 1..].contains(card) {
+                return Err(EuchreError::InvalidDeal(format!("Invalid position: card {} appears more than once", card)))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The result of solving a `Position`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Solution {
+    /// The player whose turn it is in the solved position
+    pub to_move: u8,
+    /// Tricks each team takes from the position on, including the current trick,
+    /// when everybody plays perfectly. Team 0 is players 0 & 2, team 1 is players 1 & 3.
+    pub tricks: [u8; 2],
+    /// Every card the player to move may play, with the tricks their team takes if they play it
+    /// and everybody plays perfectly afterwards
+    pub card_values: Vec<(Card, u8)>,
+}
+
+impl Solution {
+    /// The cards the player to move can play to reach the best result for their team.
+    pub fn best_cards(&self) -> Vec<Card> {
+        let best: u8 = self.tricks[usize::from(team(self.to_move))];
+        self.card_values.iter().filter(|(_, x)| *x == best).map(|(card, _)| *card).collect()
+    }
+}
+
+/// Solve `position`, see the module documentation.
+/// Returns an error if the position can't be reached in a hand of euchre.
+pub fn solve(position: &Position) -> Result<Solution, EuchreError> {
+    position.validate()?;
+    let mut search: Search = Search::new(position);
+    let to_move: u8 = search.to_move();
+    let remaining: u8 = position.hands[usize::from(position.leader)].len() as u8 + u8::from(!position.center.is_empty());
+
+    let mut card_values: Vec<(Card, u8)> = vec![];
+    for card in search.legal_cards(to_move) {
+        let team_0: u8 = search.play(card, -1, 6) as u8;
+        card_values.push((card, if team(to_move) == 0 {team_0} else {remaining - team_0}));
+    }
+    let best: u8 = card_values.iter().map(|(_, x)| *x).max().unwrap();
+    let tricks: [u8; 2] = if team(to_move) == 0 {[best, remaining - best]} else {[remaining - best, best]};
+
+    Ok(Solution { to_move, tricks, card_values })
+}
+
+/// The team of `player`, 0 for players 0 & 2 and 1 for players 1 & 3.
+fn team(player: u8) -> u8 {
+    player % 2
+}
+
+/// The mutable state of an alpha-beta search, played forward and taken back card by card.
+struct Search {
+    hands: Vec<Vec<Card>>,
+    trump: Suit,
+    judger: Judger,
+    /// The players of the current trick, in the order they play
+    order: Vec<u8>,
+    center: Vec<Card>,
+    /// Lower and upper bounds on the tricks team 0 takes from the start of a trick,
+    /// keyed by the cards left and the leader
+    bounds: HashMap<u32, (i8, i8)>,
+}
+
+impl Search {
+    fn new(position: &Position) -> Search {
+        Search {
+            hands: position.hands.clone(),
+            trump: position.trump,
+            judger: Judger::new(),
+            order: position.order_from(position.leader),
+            center: position.center.clone(),
+            bounds: HashMap::new(),
+        }
+    }
+
+    /// Identifies the start of a trick by the cards still held and who leads.
+    fn trick_key(&self) -> u32 {
+        let cards: u32 = self.hands.iter().flatten()
+            .map(|x| 1 << (Action::card_to_action(x, true).unwrap() as u32 - 6))
+            .sum();
+        cards | u32::from(self.order[0]) << 24
+    }
+
+    fn to_move(&self) -> u8 {
+        self.order[self.center.len()]
+    }
+
+    /// Cards `player` may play to the current trick, strongest first to help pruning.
+    fn legal_cards(&self, player: u8) -> Vec<Card> {
+        let hand: &Vec<Card> = &self.hands[usize::from(player)];
+        let mut cards: Vec<Card> = match self.center.first().map(|x| x.effective_suit(self.trump)) {
+            Some(led) if hand.iter().any(|x| x.effective_suit(self.trump) == led) => {
+                hand.iter().copied().filter(|x| x.effective_suit(self.trump) == led).collect()
+            },
+            _ => hand.clone(),
+        };
+        let trump: Suit = self.trump;
+        cards.sort_by_key(|x| std::cmp::Reverse((x.effective_suit(trump) == trump, x.is_right(trump), x.is_left(trump), x.rank() as u8)));
+        cards
+    }
+
+    /// Tricks team 0 takes from now on.
+    /// At the start of a trick, bounds from earlier searches of the same cards are reused.
+    fn search(&mut self, alpha: i8, beta: i8) -> i8 {
+        if !self.center.is_empty() {
+            return self.search_moves(alpha, beta)
+        }
+        if self.hands[usize::from(self.to_move())].is_empty() {
+            return 0
+        }
+
+        let key: u32 = self.trick_key();
+        let (mut lower, mut upper) = self.bounds.get(&key).copied().unwrap_or((0, 5));
+        if lower >= beta || lower == upper {
+            return lower
+        }
+        if upper <= alpha {
+            return upper
+        }
+        let value: i8 = self.search_moves(alpha.max(lower), beta.min(upper));
+        if value <= alpha.max(lower) {
+            upper = value;
+        } else if value >= beta.min(upper) {
+            lower = value;
+        } else {
+            (lower, upper) = (value, value);
+        }
+        self.bounds.insert(key, (lower, upper));
+        value
+    }
+
+    /// Alpha-beta over the current player's legal cards.
+    fn search_moves(&mut self, mut alpha: i8, mut beta: i8) -> i8 {
+        let player: u8 = self.to_move();
+        let maximizing: bool = team(player) == 0;
+        let mut best: i8 = if maximizing {-1} else {6};
+
+        for card in self.legal_cards(player) {
+            let value: i8 = self.play(card, alpha, beta);
+            if maximizing {
+                best = best.max(value);
+                alpha = alpha.max(value);
+            } else {
+                best = best.min(value);
+                beta = beta.min(value);
+            }
+            if alpha >= beta {
+                break
+            }
+        }
+        best
+    }
+
+    /// Play `card` for the current player, search the rest of the hand, then take the card back.
+    /// Returns the tricks team 0 takes from now on.
+    fn play(&mut self, card: Card, alpha: i8, beta: i8) -> i8 {
+        let player: u8 = self.to_move();
+        let hand: &mut Vec<Card> = &mut self.hands[usize::from(player)];
+        let index: usize = hand.iter().position(|x| *x == card).unwrap();
+        hand.remove(index);
+        self.center.push(card);
+
+        let value: i8 = if self.center.len() == self.order.len() {
+            let winner: u8 = self.judger.judge_trick(self.trump, &self.center, &self.order);
+            let won: i8 = i8::from(team(winner) == 0);
+            let center: Vec<Card> = std::mem::take(&mut self.center);
+            let order: Vec<u8> = self.order.clone();
+            self.order.rotate_left(order.iter().position(|x| *x == winner).unwrap());
+
+            let value: i8 = won + self.search(alpha - won, beta - won);
+            self.center = center;
+            self.order = order;
+            value
+        } else {
+            self.search(alpha, beta)
+        };
+
+        self.center.pop();
+        self.hands[usize::from(player)].insert(index, card);
+        value
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rand::prelude::*;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::dealer::{Deal, DealSpec, Dealer};
+
+    fn cards(text: &str) -> Vec<Card> {
+        text.split_whitespace().map(|x| Card::from_str(x).unwrap()).collect()
+    }
+
+    /// Plain minimax without pruning, to check the solver against.
+    fn brute_force(search: &mut Search) -> u8 {
+        let player: u8 = search.to_move();
+        if search.hands[usize::from(player)].is_empty() {
+            return 0
+        }
+        let values = search.legal_cards(player).into_iter().map(|card| {
+            let hand: &mut Vec<Card> = &mut search.hands[usize::from(player)];
+            let index: usize = hand.iter().position(|x| *x == card).unwrap();
+            hand.remove(index);
+            search.center.push(card);
+            let value: u8 = if search.center.len() == search.order.len() {
+                let winner: u8 = search.judger.judge_trick(search.trump, &search.center, &search.order);
+                let (center, order) = (std::mem::take(&mut search.center), search.order.clone());
+                search.order.rotate_left(order.iter().position(|x| *x == winner).unwrap());
+                let value: u8 = u8::from(team(winner) == 0) + brute_force(search);
+                (search.center, search.order) = (center, order);
+                value
+            } else {
+                brute_force(search)
+            };
+            search.center.pop();
+            search.hands[usize::from(player)].insert(index, card);
+            value
+        }).collect::<Vec<u8>>();
+        if team(player) == 0 {*values.iter().max().unwrap()} else {*values.iter().min().unwrap()}
+    }
+
+    fn random_position(seed: u64, tricks_left: usize) -> Position {
+        let mut gen: ChaCha8Rng = ChaCha8Rng::seed_from_u64(seed);
+        let deal: Deal = Dealer::new(&mut gen).deal_with_spec(&DealSpec::new()).unwrap();
+        let hands: Vec<Vec<Card>> = deal.hands.into_iter().map(|x| x[..tricks_left].to_vec()).collect();
+        let trump: Suit = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades][gen.gen_range(0..4)];
+        Position::new(hands, trump, gen.gen_range(0..4))
+    }
+
+    #[test]
+    fn all_trump_takes_every_trick() {
+        let hands: Vec<Vec<Card>> = vec![cards("HJ DJ HA HK HQ"), cards("S9 ST SQ SK SA"),
+                                         cards("C9 CT CQ CK CA"), cards("D9 DT DQ DK DA")];
+        let solution: Solution = solve(&Position::new(hands, Suit::Hearts, 1)).unwrap();
+        assert_eq!(solution.to_move, 1);
+        assert_eq!(solution.tricks, [5, 0]);
+        assert!(solution.card_values.iter().all(|(_, x)| *x == 0));
+    }
+
+    #[test]
+    fn values_each_card() {
+        // Player 0 leads with two tricks left; leading the ace of trump draws player 1's king,
+        // while leading the spade ace lets player 1 trump it
+        let hands: Vec<Vec<Card>> = vec![cards("HA SA"), cards("HK DA"), cards("C9 CT"), cards("D9 DT")];
+        let solution: Solution = solve(&Position::new(hands, Suit::Hearts, 0)).unwrap();
+        assert_eq!(solution.tricks, [2, 0]);
+        assert_eq!(solution.card_values, vec![(Card::from_str("HA").unwrap(), 2), (Card::from_str("SA").unwrap(), 1)]);
+        assert_eq!(solution.best_cards(), cards("HA"));
+    }
+
+    #[test]
+    fn mid_trick_and_alone() {
+        // Player 1 goes alone with player 3 sitting out; player 0 led the spade ace and player 1 is void
+        let hands: Vec<Vec<Card>> = vec![cards("S9"), cards("HJ H9"), cards("ST SK"), vec![]];
+        let position: Position = Position {
+            sitting_out: Some(3),
+            center: cards("SA"),
+            ..Position::new(hands, Suit::Hearts, 0)
+        };
+        let solution: Solution = solve(&position).unwrap();
+        assert_eq!(solution.to_move, 1);
+        assert_eq!(solution.tricks, [0, 2]);
+        assert_eq!(solution.card_values, vec![(Card::from_str("HJ").unwrap(), 2), (Card::from_str("H9").unwrap(), 2)]);
+    }
+
+    #[test]
+    fn solves_games() {
+        let mut game: EuchreGame = EuchreGame::new(Some(0), Some(4), crate::rules::RuleSet::default());
+        assert!(Position::from_game(&game).is_err());
+        while !game.get_legal_actions()[0].is_play() {
+            let action: Action = *game.get_legal_actions().last().unwrap();
+            game.step(action).unwrap();
+        }
+
+        // Play the solver's choice for every player, which must keep the value of the hand
+        let solution: Solution = solve(&Position::from_game(&game).unwrap()).unwrap();
+        let mut tricks: [u8; 2] = [0, 0];
+        while !game.is_over() {
+            let best: Card = solve(&Position::from_game(&game).unwrap()).unwrap().best_cards()[0];
+            game.step(Action::card_to_action(&best, true).unwrap()).unwrap();
+            if let Some(trick) = game.get_last_trick() {
+                tricks[usize::from(team(trick.winner))] += 1;
+            }
+        }
+        assert_eq!(tricks, solution.tricks);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        for seed in 0..30 {
+            let position: Position = random_position(seed, 3);
+            let solution: Solution = solve(&position).unwrap();
+            let expected: u8 = brute_force(&mut Search::new(&position));
+            assert_eq!(solution.tricks, [expected, 3 - expected], "seed {}", seed);
+
+            for (card, value) in solution.card_values {
+                let mut after: Position = Position { center: vec![card], ..position.clone() };
+                after.hands[usize::from(position.leader)].retain(|x| *x != card);
+                let team_0: u8 = brute_force(&mut Search::new(&after));
+                assert_eq!(value, if team(position.leader) == 0 {team_0} else {3 - team_0}, "seed {}", seed);
+            }
+        }
+    }
+
+    #[test]
+    fn solves_full_hands() {
+        for seed in 0..5 {
+            let solution: Solution = solve(&random_position(seed, 5)).unwrap();
+            assert_eq!(solution.tricks[0] + solution.tricks[1], 5);
+            assert_eq!(solution.card_values.len(), 5);
+        }
+    }
+
+    #[test]
+    fn rejects_impossible_positions() {
+        let hands: Vec<Vec<Card>> = vec![cards("HA SA"), cards("H9 ST"), cards("C9 CT"), cards("D9")];
+        assert!(solve(&Position::new(hands, Suit::Hearts, 0)).is_err());
+        let hands: Vec<Vec<Card>> = vec![cards("HA"), cards("HA"), cards("C9"), cards("D9")];
+        assert!(solve(&Position::new(hands, Suit::Hearts, 0)).is_err());
+        let hands: Vec<Vec<Card>> = vec![cards("HA"), cards("H9"), cards("C9"), cards("D9")];
+        assert!(solve(&Position { sitting_out: Some(2), ..Position::new(hands, Suit::Hearts, 0) }).is_err());
+
+        let hands: Vec<Vec<Card>> = vec![cards("HA"), cards("H9"), cards("C9"), cards("D9")];
+        assert!(solve(&Position::new(hands.clone(), Suit::Unset, 0)).is_err());
+        let unset: Vec<Vec<Card>> = vec![cards("HA"), vec![Card::new(Suit::Hearts, Rank::Unset)], cards("C9"), cards("D9")];
+        assert!(solve(&Position::new(unset, Suit::Hearts, 0)).is_err());
+        let led: Vec<Vec<Card>> = vec![cards("HA"), cards("H9"), cards("C9"), vec![]];
+        assert!(solve(&Position { center: vec![Card::new(Suit::Unset, Rank::Nine)], ..Position::new(led, Suit::Hearts, 3) }).is_err());
+    }
+}
@@ -8,3 +8,12 @@ pub fn get_rdm_gen(seed: Option<u64>) -> ChaCha8Rng {
         None => {ChaCha8Rng::from_entropy()}
     }
 }
+
+/// Derive the `index`th seed of a sequence started by `master`.  
+/// Nearby masters and indices give unrelated seeds, using the SplitMix64 finalizer.
+pub fn derive_seed(master: u64, index: u64) -> u64 {
+    let mut x: u64 = master.wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}